@@ -0,0 +1,230 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::{read_to_string, write},
+    io::ErrorKind,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{Context, Result};
+
+use crate::puzzle::Answer;
+
+/// Environment variable overriding the location of the answers file.
+pub const ANSWERS_ENV: &str = "AOC_ANSWERS";
+/// Name of the answers file inside the data directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Accepted answers per day and part, stored as
+///
+/// ```toml
+/// [day_02]
+/// part_1 = "421"
+/// part_2 = "476"
+/// ```
+///
+/// The comment block at the top of the file is kept when it is saved again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownAnswers {
+    /// leading comment lines, `#` included
+    header: Vec<String>,
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+/// Header of a newly created answers file.
+const DEFAULT_HEADER: [&str; 2] = [
+    "# Accepted answers for `verify`, one [day_XX] section per day.",
+    "# Update with `verify --record` after a solver's answer has been accepted.",
+];
+
+impl Default for KnownAnswers {
+    fn default() -> Self {
+        KnownAnswers {
+            header: DEFAULT_HEADER.map(String::from).to_vec(),
+            answers: BTreeMap::new(),
+        }
+    }
+}
+
+impl KnownAnswers {
+    /// A missing file counts as an empty store, so the first recorded answer creates it.
+    pub fn load(path: &Path) -> Result<Self> {
+        match read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .context(format!("invalid answers file '{}'", path.display())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(KnownAnswers::default()),
+            Err(error) => {
+                Err(error).context(format!("could not read answers file '{}'", path.display()))
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write(path, self.to_string())
+            .context(format!("could not write answers file '{}'", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: &Answer) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+}
+
+impl FromStr for KnownAnswers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let table: toml::Table = s.parse()?;
+        let header = s
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with('#'))
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+        let mut answers = BTreeMap::new();
+
+        for (section, values) in table {
+            let day = section
+                .strip_prefix("day_")
+                .and_then(|day| day.parse::<u8>().ok())
+                .context(format!("expected a [day_XX] section, got '{}'", section))?;
+            let values = values
+                .as_table()
+                .context(format!("section '{}' is not a table", section))?;
+
+            for (key, value) in values {
+                let part = key
+                    .strip_prefix("part_")
+                    .and_then(|part| part.parse::<u8>().ok())
+                    .context(format!("expected part_1 or part_2, got '{}'", key))?;
+                let value = match value {
+                    toml::Value::String(text) => text.clone(),
+                    value => value.to_string(),
+                };
+                answers.insert((day, part), value);
+            }
+        }
+
+        Ok(KnownAnswers { header, answers })
+    }
+}
+
+impl Display for KnownAnswers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut table = toml::Table::new();
+        self.answers.iter().for_each(|(&(day, part), answer)| {
+            let section = table
+                .entry(format!("day_{:02}", day))
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(section) = section {
+                section.insert(
+                    format!("part_{}", part),
+                    toml::Value::String(answer.clone()),
+                );
+            }
+        });
+
+        for line in &self.header {
+            writeln!(f, "{}", line)?;
+        }
+        if !self.header.is_empty() && !table.is_empty() {
+            writeln!(f)?;
+        }
+        write!(f, "{}", table)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// no accepted answer recorded yet
+    Missing,
+}
+
+impl Status {
+    pub fn of(expected: Option<&str>, actual: &Result<Answer>) -> Status {
+        match (expected, actual) {
+            (None, _) => Status::Missing,
+            (Some(expected), Ok(actual)) if actual.to_string() == expected => Status::Pass,
+            (Some(_), _) => Status::Fail,
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_round_trip_through_toml() {
+        let mut answers = KnownAnswers::default();
+        answers.insert(2, 1, &Answer::from(421));
+        answers.insert(17, 1, &Answer::Text("4,6,3".to_string()));
+        answers.insert(18, 2, &Answer::Coordinate(6, 1));
+
+        let parsed: KnownAnswers = answers.to_string().parse().unwrap();
+        assert_eq!(parsed, answers);
+        assert_eq!(parsed.get(18, 2), Some("6,1"));
+        assert_eq!(parsed.get(18, 1), None);
+    }
+
+    #[test]
+    fn integer_values_are_accepted() {
+        let answers: KnownAnswers = "[day_02]\npart_1 = 421\n".parse().unwrap();
+        assert_eq!(answers.get(2, 1), Some("421"));
+    }
+
+    #[test]
+    fn status_compares_display_values() {
+        let actual = Ok(Answer::from(421));
+        assert_eq!(Status::of(Some("421"), &actual), Status::Pass);
+        assert_eq!(Status::of(Some("420"), &actual), Status::Fail);
+        assert_eq!(Status::of(None, &actual), Status::Missing);
+        assert_eq!(
+            Status::of(Some("421"), &Err(anyhow::anyhow!("no input"))),
+            Status::Fail
+        );
+    }
+
+    #[test]
+    fn seeded_answers_parse() {
+        let answers: KnownAnswers = include_str!("answers.toml").parse().unwrap();
+        assert_eq!(answers.get(2, 1), Some("421"));
+        assert_eq!(answers.get(2, 2), Some("476"));
+    }
+
+    #[test]
+    fn saving_keeps_the_header_comment() {
+        let seeded = include_str!("answers.toml");
+        let mut answers: KnownAnswers = seeded.parse().unwrap();
+        assert_eq!(answers.to_string(), seeded);
+
+        answers.insert(1, 1, &Answer::from(11));
+        let saved = answers.to_string();
+        assert!(saved.starts_with("# Accepted answers for `verify`"));
+        assert!(saved.contains("verify --record"));
+        assert_eq!(saved.parse::<KnownAnswers>().unwrap(), answers);
+
+        // a new file starts with the same header
+        assert!(KnownAnswers::default()
+            .to_string()
+            .starts_with("# Accepted answers for `verify`"));
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    fmt::{self, Display},
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{params::ParamSettings, registry::Day};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Parse,
+    PartOne,
+    PartTwo,
+}
+
+impl Stage {
+    fn of_part(part: u8) -> Stage {
+        match part {
+            1 => Stage::PartOne,
+            _ => Stage::PartTwo,
+        }
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::PartOne => write!(f, "part 1"),
+            Stage::PartTwo => write!(f, "part 2"),
+        }
+    }
+}
+
+/// Summary of repeated timings, all durations in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        let runs = nanos.len();
+        let median = match runs % 2 {
+            0 => (nanos[runs / 2 - 1] + nanos[runs / 2]) / 2.0,
+            _ => nanos[runs / 2],
+        };
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        Some(Stats {
+            runs,
+            min_ns: nanos[0] as u64,
+            median_ns: median as u64,
+            mean_ns: mean as u64,
+            stddev_ns: variance.sqrt() as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchEntry {
+    pub day: u8,
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub entries: Vec<BenchEntry>,
+}
+
+/// Runs the day `runs` times, a failing part aborts the benchmark.
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    settings: &ParamSettings,
+    parts: &[u8],
+    runs: usize,
+) -> Result<Vec<BenchEntry>> {
+    let mut samples: Vec<(Stage, Vec<Duration>)> = [Stage::Parse]
+        .into_iter()
+        .chain(parts.iter().map(|&part| Stage::of_part(part)))
+        .map(|stage| (stage, vec![]))
+        .collect();
+
+    for _ in 0..runs {
+        let run = day.run(input, settings, parts)?;
+        samples[0].1.push(run.parse);
+
+        for (i, part) in run.parts.into_iter().enumerate() {
+            part.answer
+                .context(format!("day {} part {} failed", day.number, part.part))?;
+            samples[i + 1].1.push(part.elapsed);
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|(stage, durations)| {
+            Ok(BenchEntry {
+                day: day.number,
+                stage,
+                stats: Stats::of(&durations).ok_or_else(|| anyhow!("no runs to benchmark"))?,
+            })
+        })
+        .collect()
+}
+
+impl BenchReport {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)?;
+        }
+        write(path, self.to_json()?)
+            .context(format!("could not write benchmark to '{}'", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = read_to_string(path)
+            .context(format!("could not read benchmark '{}'", path.display()))?;
+        serde_json::from_str(&text).context(format!("invalid benchmark '{}'", path.display()))
+    }
+
+    /// Replaces the entries of the same day and stage, keeping the others.
+    pub fn merge(&mut self, other: BenchReport) {
+        self.entries.retain(|entry| {
+            !other
+                .entries
+                .iter()
+                .any(|new| new.day == entry.day && new.stage == entry.stage)
+        });
+        self.entries.extend(other.entries);
+        self.entries.sort_by_key(|entry| (entry.day, entry.stage));
+    }
+
+    fn find(&self, day: u8, stage: Stage) -> Option<&BenchEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.stage == stage)
+    }
+}
+
+/// Directory for named baselines, kept next to the build artifacts since timings are machine specific.
+pub fn default_baseline_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("baselines")
+}
+
+pub fn baseline_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Unchanged,
+    Slower,
+    /// not part of the baseline
+    New,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Faster => write!(f, "faster"),
+            Verdict::Unchanged => write!(f, "ok"),
+            Verdict::Slower => write!(f, "SLOWER"),
+            Verdict::New => write!(f, "new"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline_ns: Option<u64>,
+    pub current_ns: u64,
+    /// relative change of the median in percent, positive means slower
+    pub change: Option<f64>,
+    pub verdict: Verdict,
+}
+
+/// Compares medians, a change beyond `threshold` percent either way is flagged.
+pub fn compare(baseline: &BenchReport, current: &BenchReport, threshold: f64) -> Vec<Comparison> {
+    current
+        .entries
+        .iter()
+        .map(|entry| {
+            let current_ns = entry.stats.median_ns;
+            let baseline_ns = baseline
+                .find(entry.day, entry.stage)
+                .map(|old| old.stats.median_ns);
+            let change = baseline_ns
+                .map(|old| (current_ns as f64 - old as f64) / (old.max(1) as f64) * 100.0);
+
+            let verdict = match change {
+                None => Verdict::New,
+                Some(change) if change > threshold => Verdict::Slower,
+                Some(change) if change < -threshold => Verdict::Faster,
+                Some(_) => Verdict::Unchanged,
+            };
+
+            Comparison {
+                day: entry.day,
+                stage: entry.stage,
+                baseline_ns,
+                current_ns,
+                change,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+pub struct ComparisonTable<'a>(pub &'a [Comparison]);
+
+impl Display for ComparisonTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:<6}  {:>10}  {:>10}  {:>8}  status",
+            "day", "stage", "baseline", "current", "change"
+        )?;
+        for comparison in self.0 {
+            writeln!(
+                f,
+                "{:>3}  {:<6}  {:>10}  {:>10}  {:>8}  {}",
+                comparison.day,
+                comparison.stage.to_string(),
+                comparison.baseline_ns.map_or("-".to_string(), nanos),
+                nanos(comparison.current_ns),
+                comparison
+                    .change
+                    .map_or("-".to_string(), |change| format!("{:+.1}%", change)),
+                comparison.verdict,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn nanos(value: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(value))
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:<6}  {:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+            "day", "stage", "runs", "min", "median", "mean", "stddev"
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:>3}  {:<6}  {:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+                entry.day,
+                entry.stage.to_string(),
+                entry.stats.runs,
+                nanos(entry.stats.min_ns),
+                nanos(entry.stats.median_ns),
+                nanos(entry.stats.mean_ns),
+                nanos(entry.stats.stddev_ns),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, stage: Stage, median_ns: u64) -> BenchEntry {
+        BenchEntry {
+            day,
+            stage,
+            stats: Stats {
+                runs: 1,
+                min_ns: median_ns,
+                median_ns,
+                mean_ns: median_ns,
+                stddev_ns: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn stats_of_samples() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].into_iter().map(Duration::from_nanos).collect();
+        let stats = Stats::of(&samples).unwrap();
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min_ns, 1);
+        assert_eq!(stats.median_ns, 2);
+        assert_eq!(stats.mean_ns, 2);
+        assert_eq!(stats.stddev_ns, 1);
+        assert!(Stats::of(&[]).is_none());
+    }
+
+    #[test]
+    fn compare_flags_changes_beyond_threshold() {
+        let baseline = BenchReport {
+            entries: vec![
+                entry(9, Stage::Parse, 100),
+                entry(9, Stage::PartOne, 100),
+                entry(9, Stage::PartTwo, 100),
+            ],
+        };
+        let current = BenchReport {
+            entries: vec![
+                entry(9, Stage::Parse, 105),
+                entry(9, Stage::PartOne, 150),
+                entry(9, Stage::PartTwo, 50),
+                entry(11, Stage::PartOne, 10),
+            ],
+        };
+        let verdicts: Vec<Verdict> = compare(&baseline, &current, 10.0)
+            .into_iter()
+            .map(|comparison| comparison.verdict)
+            .collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Unchanged,
+                Verdict::Slower,
+                Verdict::Faster,
+                Verdict::New
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_replaces_matching_entries() {
+        let mut baseline = BenchReport {
+            entries: vec![entry(9, Stage::Parse, 100), entry(11, Stage::Parse, 100)],
+        };
+        baseline.merge(BenchReport {
+            entries: vec![entry(9, Stage::Parse, 50)],
+        });
+        assert_eq!(baseline.entries.len(), 2);
+        assert_eq!(baseline.entries[0].stats.median_ns, 50);
+    }
+}
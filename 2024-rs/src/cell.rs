@@ -0,0 +1,103 @@
+use anyhow::{anyhow, Result};
+
+/// Value drawn as a single character, e.g. a cell of a puzzle grid.
+pub trait CharCell: Sized {
+    /// what a cell is called in error messages, e.g. `maze cell`
+    const NAME: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(&self) -> char;
+
+    fn parse_char(c: char) -> Result<Self> {
+        Self::from_char(c).ok_or_else(|| anyhow!("unknown {} '{}'", Self::NAME, c))
+    }
+}
+
+/// Declares a fieldless enum together with its [`CharCell`] and `Display` impls,
+/// so the character of every variant is written down exactly once.
+///
+/// `Variant = 'c'` is parsed and drawn as `c`. `Variant => 'c'` is only ever drawn, e.g. a
+/// marker added while solving, so parsing rejects its character.
+#[macro_export]
+macro_rules! char_cell {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident: $label:literal {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident $(= $c:literal)? $(=> $drawn:literal)?
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant),+
+        }
+
+        impl $crate::cell::CharCell for $name {
+            const NAME: &'static str = $label;
+
+            fn from_char(c: char) -> Option<Self> {
+                match c {
+                    $($($c => Some($name::$variant),)?)+
+                    _ => None,
+                }
+            }
+
+            fn to_char(&self) -> char {
+                match self {
+                    $($name::$variant => $($c)? $($drawn)?),+
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                use $crate::cell::CharCell;
+                write!(f, "{}", self.to_char())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    char_cell! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Tile: "tile" {
+            Wall = '#',
+            Floor = '.',
+            /// only ever drawn, never parsed from the input
+            Seen => 'O',
+        }
+    }
+
+    #[test]
+    fn one_definition_for_both_directions() {
+        let grid: Grid<Tile> = Grid::parse_cells("#.\n.#").unwrap();
+        assert_eq!(grid[(0, 1)], Tile::Floor);
+
+        let seen = grid.map(|&tile| match tile {
+            Tile::Floor => Tile::Seen,
+            tile => tile,
+        });
+        assert_eq!(seen.to_string(), "#O\nO#");
+        assert!(Grid::<Tile>::parse_cells("#O").is_err());
+    }
+
+    #[test]
+    fn unknown_characters_are_named() {
+        assert_eq!(
+            Tile::parse_char('x').unwrap_err().to_string(),
+            "unknown tile 'x'"
+        );
+        assert_eq!(
+            Grid::<Tile>::parse_cells("#.\n.x").unwrap_err().to_string(),
+            "line 2, column 2: unknown tile 'x'"
+        );
+    }
+}
@@ -1,70 +1,89 @@
 pub mod solution {
-    use std::{
-        collections::HashMap,
-        fs::File,
-        io::{BufRead, BufReader},
-        iter::zip,
+    use anyhow::Result;
+    use std::{collections::HashMap, iter::zip};
+
+    use crate::{
+        parse,
+        puzzle::{Answer, Solution},
     };
 
-    fn read_from_file(file_path: &str) -> (Vec<i32>, Vec<i32>) {
-        let file = File::open(file_path).expect("file not found!");
-        let reader = BufReader::new(file);
+    pub struct Day01;
 
+    fn read_lists(input: &str) -> Result<(Vec<i32>, Vec<i32>)> {
         let mut first = vec![];
         let mut second = vec![];
 
-        reader
-            .lines()
-            .map(|line| {
-                line.unwrap()
-                    .split("   ")
-                    .map(|number| number.parse::<i32>().unwrap())
-                    .collect::<Vec<i32>>()
-            })
-            .for_each(|numbers| {
-                first.push(numbers[0]);
-                second.push(numbers[1]);
-            });
+        for line in parse::lines(input) {
+            let [left, right] = line.ints_n::<i32, 2>()?;
+            first.push(left);
+            second.push(right);
+        }
 
-        (first, second)
+        Ok((first, second))
     }
 
-    fn part_one() {
-        let numbers = read_from_file("./src/day_01/input.txt");
+    impl Solution for Day01 {
+        type Input = (Vec<i32>, Vec<i32>);
+        type Params = ();
 
-        let mut first = numbers.0.to_vec();
-        let mut second = numbers.1.to_vec();
+        fn parse(input: &str) -> Result<Self::Input> {
+            read_lists(input)
+        }
 
-        first.sort();
-        second.sort();
+        fn part_one(numbers: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            let mut first = numbers.0.to_vec();
+            let mut second = numbers.1.to_vec();
 
-        let result = zip(first, second)
-            .map(|value| (value.0 - value.1).abs())
-            .fold(0, |a, b| a + b);
+            first.sort();
+            second.sort();
 
-        println!("part one: {}", result);
-    }
+            let result = zip(first, second)
+                .map(|value| (value.0 - value.1).abs())
+                .sum::<i32>();
 
-    fn part_two() {
-        let numbers = read_from_file("./src/day_01/input.txt");
+            Ok(result.into())
+        }
 
-        let first = numbers.0;
-        let second = numbers.1;
+        fn part_two(numbers: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            let first = &numbers.0;
+            let second = &numbers.1;
 
-        let count: HashMap<i32, i32> = second.iter().fold(HashMap::new(), |mut acc, number| {
-            *acc.entry(*number).or_insert(0) += 1;
-            acc
-        });
+            let count: HashMap<i32, i32> = second.iter().fold(HashMap::new(), |mut acc, number| {
+                *acc.entry(*number).or_insert(0) += 1;
+                acc
+            });
 
-        let result = first.iter().fold(0, |acc, number| {
-            acc + number * count.get(number).unwrap_or(&0)
-        });
+            let result = first.iter().fold(0, |acc, number| {
+                acc + number * count.get(number).unwrap_or(&0)
+            });
 
-        println!("part two: {}", result);
+            Ok(result.into())
+        }
     }
 
-    pub fn solve() {
-        part_one();
-        part_two();
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+        #[test]
+        fn part_one_example() {
+            let input = Day01::parse(EXAMPLE).unwrap();
+            assert_eq!(Day01::part_one(&input, &()).unwrap(), Answer::from(11));
+        }
+
+        #[test]
+        fn part_two_example() {
+            let input = Day01::parse(EXAMPLE).unwrap();
+            assert_eq!(Day01::part_two(&input, &()).unwrap(), Answer::from(31));
+        }
     }
 }
@@ -1,19 +1,16 @@
 pub mod solution {
-    use std::{
-        fs::File,
-        io::{BufRead, BufReader},
-    };
+    use anyhow::{Context, Result};
 
-    fn read_from_file(file_path: &str) -> Vec<Vec<i32>> {
-        let file = File::open(file_path).expect("file not found!");
-        let reader = BufReader::new(file);
+    use crate::puzzle::{Answer, Solution};
 
-        reader
+    pub struct Day02;
+
+    fn read_reports(input: &str) -> Result<Vec<Vec<i32>>> {
+        input
             .lines()
             .map(|line| {
-                line.unwrap()
-                    .split(" ")
-                    .map(|item| item.parse::<i32>().unwrap())
+                line.split(" ")
+                    .map(|item| item.parse::<i32>().context("could not parse level"))
                     .collect()
             })
             .collect()
@@ -34,19 +31,6 @@ pub mod solution {
         })
     }
 
-    fn part_one() {
-        let data = read_from_file("./src/day_02/input.txt");
-        let result = data
-            .iter()
-            .filter(|nums| {
-                (is_increasing(nums) || is_decreasing(nums)) && is_within_diff_range(nums)
-            })
-            .count();
-
-        println!("part one: {}", result);
-        assert_eq!(result, 421);
-    }
-
     fn is_tolerable_ok(nums: &[i32]) -> bool {
         nums.iter().enumerate().any(|(i, _)| {
             let mut copy = nums.to_vec();
@@ -55,15 +39,55 @@ pub mod solution {
         })
     }
 
-    fn part_two() {
-        let data = read_from_file("./src/day_02/input.txt");
-        let result = data.iter().filter(|nums| is_tolerable_ok(nums)).count();
-        println!("part two: {}", result);
-        assert_eq!(result, 476);
+    impl Solution for Day02 {
+        type Input = Vec<Vec<i32>>;
+        type Params = ();
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            read_reports(input)
+        }
+
+        fn part_one(data: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            let result = data
+                .iter()
+                .filter(|nums| {
+                    (is_increasing(nums) || is_decreasing(nums)) && is_within_diff_range(nums)
+                })
+                .count();
+
+            Ok(result.into())
+        }
+
+        fn part_two(data: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            let result = data.iter().filter(|nums| is_tolerable_ok(nums)).count();
+
+            Ok(result.into())
+        }
     }
 
-    pub fn solve() {
-        part_one();
-        part_two();
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+        #[test]
+        fn part_one_example() {
+            let input = Day02::parse(EXAMPLE).unwrap();
+            assert_eq!(Day02::part_one(&input, &()).unwrap(), Answer::from(2));
+        }
+
+        #[test]
+        fn part_two_example() {
+            let input = Day02::parse(EXAMPLE).unwrap();
+            assert_eq!(Day02::part_two(&input, &()).unwrap(), Answer::from(4));
+        }
     }
 }
@@ -1,14 +1,16 @@
 pub mod solution {
-    use anyhow::{Context, Error};
+    use anyhow::{Context, Error, Result};
     use regex::Regex;
-    use std::fs::read_to_string;
 
-    fn part_one() -> Result<(), Error> {
-        let data = read_to_string("./src/day_03/input.txt")?;
+    use crate::puzzle::{Answer, Solution};
+
+    pub struct Day03;
+
+    fn sum_multiplications(data: &str) -> Result<i32, Error> {
         let re = Regex::new(r"mul\((?<left>\d+)\,(?<right>\d+)\)")?;
 
         let result: i32 = re
-            .captures_iter(data.as_str())
+            .captures_iter(data)
             .map(|capture| {
                 let left: i32 = capture
                     .name("left")
@@ -26,58 +28,84 @@ pub mod solution {
             .into_iter()
             .sum();
 
-        println!("part one result {}", result);
-
-        Ok(())
+        Ok(result)
     }
 
-    fn part_two() -> Result<(), Error> {
-        let data = read_to_string("./src/day_03/input.txt")?;
+    fn sum_enabled_multiplications(data: &str) -> Result<i32, Error> {
         let re = Regex::new(r"mul\((?<left>\d+)\,(?<right>\d+)\)|do\(\)|don\'t\(\)")?;
 
         let mut is_doing = true;
         let mut result = 0;
 
-        re.captures_iter(data.as_str()).for_each(|capture| {
-            let slice = capture.get(0).unwrap().as_str();
+        for capture in re.captures_iter(data) {
+            let slice = capture.get(0).context("match not found")?.as_str();
 
             match slice {
                 "don't()" => {
                     is_doing = false;
-                    return;
                 }
                 "do()" => {
                     is_doing = true;
-                    return;
-                }
-                _ if !is_doing => {
-                    return;
                 }
+                _ if !is_doing => {}
                 _ => {
                     let left: i32 = capture
                         .name("left")
-                        .expect("left not found")
+                        .context("left not found")?
                         .as_str()
-                        .parse::<i32>()
-                        .unwrap();
+                        .parse()?;
                     let right: i32 = capture
                         .name("right")
-                        .expect("right not found")
+                        .context("right not found")?
                         .as_str()
-                        .parse::<i32>()
-                        .unwrap();
+                        .parse()?;
                     result += left * right;
                 }
             }
-        });
+        }
+
+        Ok(result)
+    }
 
-        println!("part two result {}", result);
+    impl Solution for Day03 {
+        type Input = String;
+        type Params = ();
 
-        Ok(())
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.to_string())
+        }
+
+        fn part_one(data: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            Ok(sum_multiplications(data)?.into())
+        }
+
+        fn part_two(data: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            Ok(sum_enabled_multiplications(data)?.into())
+        }
     }
 
-    pub fn solve() {
-        let _ = part_one();
-        let _ = part_two();
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const EXAMPLE: &str = "\
+xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
+";
+
+        const EXAMPLE_PART_TWO: &str = "\
+xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+";
+
+        #[test]
+        fn part_one_example() {
+            let input = Day03::parse(EXAMPLE).unwrap();
+            assert_eq!(Day03::part_one(&input, &()).unwrap(), Answer::from(161));
+        }
+
+        #[test]
+        fn part_two_example() {
+            let input = Day03::parse(EXAMPLE_PART_TWO).unwrap();
+            assert_eq!(Day03::part_two(&input, &()).unwrap(), Answer::from(48));
+        }
     }
 }
@@ -1,135 +1,100 @@
-use std::{fs::read_to_string, iter::repeat};
+use anyhow::Result;
 
-use anyhow::{Context, Error, Result};
+use crate::{
+    direction::Dir8,
+    grid::Grid,
+    puzzle::{Answer, Solution},
+};
 
-fn read_board(file_path: &str) -> Result<Vec<Vec<char>>> {
-    let data = read_to_string(file_path).context("could not read file")?;
-    let board = data
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect();
-
-    Ok(board)
+fn read_board(input: &str) -> Result<Grid<char>> {
+    Grid::parse(input, Ok)
 }
 
-fn part_one() -> Result<(), Error> {
-    let board = read_board("./src/day_04/input.txt")?;
-
-    let n = board.len();
-    let m = board[0].len();
-
-    let letters: Vec<char> = "XMAS".chars().collect();
-
-    let mut result = 0;
-
-    let dxdy: Vec<i32> = vec![-1, 0, 1];
-
-    let mut new_board: Vec<Vec<char>> = repeat(repeat('.').take(m).collect()).take(n).collect();
+pub struct Day04;
 
-    for ui in 0..n {
-        for uj in 0..m {
-            dxdy.iter().for_each(|dx| {
-                dxdy.iter().for_each(|dy| {
-                    let mut i = ui;
-                    let mut j = uj;
-                    let mut ok = true;
-                    let mut history = vec![(i, j)];
-                    for l in 0..letters.len() {
-                        if board[i][j] != letters[l] {
-                            ok = false;
-                            break;
-                        }
-                        if l + 1 == letters.len() {
-                            break;
-                        }
-                        let ni = i as i32 + *dx;
-                        let nj = j as i32 + *dy;
+impl Solution for Day04 {
+    type Input = Grid<char>;
+    type Params = ();
 
-                        if ni < 0 || ni >= n as i32 || nj < 0 || nj >= m as i32 {
-                            ok = false;
-                            break;
-                        }
-                        if ni == i as i32 && nj == j as i32 {
-                            ok = false;
-                            break;
-                        }
-                        i = ni as usize;
-                        j = nj as usize;
-                        history.push((i, j));
-                    }
-                    if ok {
-                        result += 1;
-                        history.iter().for_each(|(ni, nj)| {
-                            new_board[*ni][*nj] = board[*ni][*nj];
-                        });
-                    }
-                })
-            });
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_board(input)
     }
 
-    board
-        .iter()
-        .for_each(|line| println!("{}", String::from_iter(line.iter())));
+    fn part_one(board: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let letters: Vec<char> = "XMAS".chars().collect();
+
+        let result = board
+            .positions()
+            .map(|start| {
+                Dir8::ALL
+                    .iter()
+                    .filter(|dir| {
+                        let word: Vec<char> = board
+                            .ray(start, dir.offset())
+                            .take(letters.len())
+                            .map(|pos| board[pos])
+                            .collect();
+                        word == letters
+                    })
+                    .count()
+            })
+            .sum::<usize>();
 
-    println!("\n");
+        Ok(result.into())
+    }
 
-    new_board
+    fn part_two(board: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let masks: Vec<Grid<char>> = [
+            "M.M\n.A.\nS.S",
+            "M.S\n.A.\nM.S",
+            "S.M\n.A.\nS.M",
+            "S.S\n.A.\nM.M",
+        ]
         .iter()
-        .for_each(|line| println!("{}", String::from_iter(line.iter())));
-
-    println!("part one result {}", result);
-
-    Ok(())
-}
-
-fn part_two() -> Result<(), Error> {
-    let board = read_board("./src/day_04/input.txt")?;
-
-    let masks: Vec<Vec<Vec<char>>> = vec![
-        "M.M\n.A.\nS.S",
-        "M.S\n.A.\nM.S",
-        "S.M\n.A.\nS.M",
-        "S.S\n.A.\nM.M",
-    ]
-    .iter()
-    .map(|mask| mask.lines().map(|line| line.chars().collect()).collect())
-    .collect();
-
-    let n = board.len();
-    let m = board[0].len();
-
-    let mut result = 0;
-
-    for i in 0..n {
-        for j in 0..m {
-            if i + 2 >= n || j + 2 >= m {
-                continue;
-            }
-            let does_mach = masks.iter().any(|mask| {
-                mask.iter().enumerate().all(|(mi, rows)| {
-                    rows.iter().enumerate().all(|(mj, col)| {
-                        if *col == '.' {
-                            return true;
-                        }
-                        return *col == board[i + mi][j + mj];
+        .map(|mask| Grid::parse(mask, Ok))
+        .collect::<Result<_>>()?;
+
+        let result = board
+            .positions()
+            .filter(|&(i, j)| {
+                masks.iter().any(|mask| {
+                    mask.iter().all(|((mi, mj), &cell)| {
+                        cell == '.' || board.get((i + mi, j + mj)) == Some(&cell)
                     })
                 })
-            });
+            })
+            .count();
 
-            if does_mach {
-                result += 1;
-            }
-        }
+        Ok(result.into())
     }
-
-    println!("part two result {}", result);
-
-    Ok(())
 }
 
-pub fn solve() -> Result<(), Error> {
-    let _ = part_one();
-    let _ = part_two();
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part_one(&input, &()).unwrap(), Answer::from(18));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day04::parse(EXAMPLE).unwrap();
+        assert_eq!(Day04::part_two(&input, &()).unwrap(), Answer::from(9));
+    }
 }
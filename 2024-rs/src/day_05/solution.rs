@@ -1,183 +1,124 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs::read_to_string,
-};
+use anyhow::{Error, Result};
 
-use anyhow::{Context, Error, Result};
+use crate::{
+    graph::Rules,
+    parse::{self, ParseResult},
+    puzzle::{Answer, Solution},
+};
 
-struct Data {
-    rules: Vec<(i32, i32)>,
+pub struct Data {
+    rules: Rules<i32>,
     updates: Vec<Vec<i32>>,
 }
 
-fn read_data_from_file(file_path: &str) -> Result<Data, Error> {
-    let input = read_to_string(file_path).context("could not read file")?;
-
-    let input_parts: Vec<&str> = input.split("\n\n").collect();
-    let rules_part = *input_parts
-        .get(0)
-        .context("could not get rules part from input")?;
-    let updates_part = *input_parts
-        .get(1)
-        .context("could not get updates part from input")?;
+fn read_data(input: &str) -> Result<Data, Error> {
+    let [rules_part, updates_part] = parse::sections_n(input)?;
 
     let rules = rules_part
         .lines()
-        .map(|line| {
-            line.split("|")
-                .map(|num| num.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .map(|nums| (nums[0], nums[1]))
-        .collect();
+        .map(|line| line.ints_n::<i32, 2>().map(|[from, to]| (from, to)))
+        .collect::<ParseResult<_>>()?;
 
     let updates = updates_part
         .lines()
-        .map(|line| {
-            line.split(",")
-                .map(|num| num.parse::<i32>().unwrap())
-                .collect()
+        .map(|line| match line.ints()? {
+            update if update.is_empty() => Err(line.error(1, "update has no pages")),
+            update => Ok(update),
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
     Ok(Data { rules, updates })
 }
 
-fn part_one() -> Result<(), Error> {
-    let data = read_data_from_file("./src/day_05/input.txt")?;
-
-    println!(
-        "rules len {}, updates len {}",
-        data.rules.len(),
-        data.updates.len()
-    );
-
-    let mut graph: HashMap<i32, HashSet<i32>> = HashMap::new();
-
-    data.rules.iter().for_each(|&(from, to)| {
-        graph.entry(from).or_insert(HashSet::new()).insert(to);
-    });
-
-    let result: i32 = data
-        .updates
-        .into_iter()
-        .filter(|update| {
-            let mut was: HashSet<i32> = HashSet::new();
-
-            let allowlist: HashSet<i32> = HashSet::from_iter(update.iter().cloned());
-
-            update.iter().rev().all(|&current| {
-                if was.contains(&current) {
-                    return false;
-                }
-                let mut queue: VecDeque<i32> = VecDeque::new();
-                queue.push_back(current);
-
-                while let Some(from) = queue.pop_front() {
-                    was.insert(from);
-                    graph
-                        .entry(from)
-                        .or_insert(HashSet::new())
-                        .iter()
-                        .filter(|to| !was.contains(*to) && allowlist.contains(*to))
-                        .for_each(|to| queue.push_back(*to));
-                }
-                return true;
-            })
-        })
-        .map(|update| update[update.len() / 2])
-        .sum();
-
-    println!("valid ones {}", result);
-
-    Ok(())
+fn middle(update: &[i32]) -> i32 {
+    update[update.len() / 2]
 }
 
-fn part_two() -> Result<(), Error> {
-    let data = read_data_from_file("./src/day_05/input.txt")?;
-
-    let mut graph: HashMap<i32, HashSet<i32>> = HashMap::new();
-
-    data.rules.iter().for_each(|&(from, to)| {
-        graph.entry(from).or_insert(HashSet::new()).insert(to);
-    });
-
-    let incorrect_ones: Vec<Vec<i32>> = data
-        .updates
-        .into_iter()
-        .filter(|update| {
-            let mut was: HashSet<i32> = HashSet::new();
-
-            let allowlist: HashSet<i32> = HashSet::from_iter(update.iter().cloned());
-
-            update.iter().rev().any(|&current| {
-                if was.contains(&current) {
-                    return true;
-                }
-                let mut queue: VecDeque<i32> = VecDeque::new();
-                queue.push_back(current);
-
-                while let Some(from) = queue.pop_front() {
-                    was.insert(from);
-                    graph
-                        .entry(from)
-                        .or_insert(HashSet::new())
-                        .iter()
-                        .filter(|to| !was.contains(*to) && allowlist.contains(*to))
-                        .for_each(|to| queue.push_back(*to));
-                }
-                return false;
-            })
-        })
-        .collect();
-
-    let corrected: Vec<Vec<i32>> = incorrect_ones
-        .into_iter()
-        .map(|update| {
-            fn dfs(
-                from: i32,
-                graph: &HashMap<i32, HashSet<i32>>,
-                allowlist: &HashSet<i32>,
-                was: &mut HashSet<i32>,
-                top_sorted: &mut Vec<i32>,
-            ) {
-                was.insert(from);
-                if let Some(neighbours) = graph.get(&from) {
-                    neighbours.iter().for_each(|to| {
-                        if was.contains(to) || !allowlist.contains(to) {
-                            return;
-                        }
-                        dfs(*to, graph, allowlist, was, top_sorted);
-                    });
-                }
-                top_sorted.push(from);
-            }
+pub struct Day05;
 
-            let allowlist: HashSet<i32> = HashSet::from_iter(update.iter().cloned());
-            let mut was: HashSet<i32> = HashSet::new();
-            let mut top_sorted: Vec<i32> = vec![];
+impl Solution for Day05 {
+    type Input = Data;
+    type Params = ();
 
-            update.iter().for_each(|num| {
-                if was.contains(num) {
-                    return;
-                }
-                dfs(*num, &graph, &allowlist, &mut was, &mut top_sorted);
-            });
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_data(input)
+    }
 
-            return top_sorted.into_iter().rev().collect();
-        })
-        .collect();
+    fn part_one(data: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let result: i32 = data
+            .updates
+            .iter()
+            .filter(|update| data.rules.check(update).is_ok())
+            .map(|update| middle(update))
+            .sum();
 
-    let result: i32 = corrected
-        .iter()
-        .map(|update| update[update.len() / 2])
-        .sum();
+        Ok(result.into())
+    }
 
-    println!("part two result {}", result);
+    fn part_two(data: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut result = 0;
+        for update in &data.updates {
+            if data.rules.check(update).is_err() {
+                result += middle(&data.rules.sort(update)?);
+            }
+        }
 
-    Ok(())
+        Ok(result.into())
+    }
 }
 
-pub fn solve() -> Result<(), Error> {
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_one(&input, &()).unwrap(), Answer::from(143));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_two(&input, &()).unwrap(), Answer::from(123));
+    }
+
+    #[test]
+    fn rejects_updates_without_pages() {
+        assert_eq!(
+            Day05::parse("47|53\n\n47,53\n,").err().unwrap().to_string(),
+            "line 4, column 1: update has no pages"
+        );
+    }
 }
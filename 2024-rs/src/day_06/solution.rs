@@ -1,220 +1,126 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
-};
+use std::collections::HashSet;
 
 use anyhow::{Context, Error, Result};
 
-fn read_room_from_file(file_path: &str) -> Result<Vec<Vec<char>>, Error> {
-    let input = read_to_string(file_path).context("could not read file")?;
-    let room = input.lines().map(|line| line.chars().collect()).collect();
-    Ok(room)
-}
-
-fn get_next_direction(direction: char) -> Result<char, Error> {
-    match direction {
-        '^' => Ok('>'),
-        '>' => Ok('V'),
-        'V' => Ok('<'),
-        '<' => Ok('^'),
-        _ => Err(Error::msg("incorrect direction")),
-    }
-}
+use crate::{
+    direction::Dir4,
+    grid::{Grid, Pos},
+    puzzle::{Answer, Solution},
+};
 
-fn get_delta_from_direction(direction: char) -> Result<(i32, i32), Error> {
-    match direction {
-        '^' => Ok((-1, 0)),
-        '>' => Ok((0, 1)),
-        'V' => Ok((1, 0)),
-        '<' => Ok((0, -1)),
-        _ => Err(Error::msg("incorrect direction")),
-    }
+fn read_room(input: &str) -> Result<Grid<char>, Error> {
+    Grid::parse(input, Ok)
 }
 
-fn is_guard(cell: char) -> bool {
-    let directions = "^>V<";
-    directions.contains(cell)
+fn find_guard(room: &Grid<char>) -> Result<(Pos, Dir4)> {
+    room.iter()
+        .find_map(|(pos, &cell)| Dir4::from_arrow(cell).map(|direction| (pos, direction)))
+        .context("no guard found")
 }
 
 fn is_obstacle(cell: char) -> bool {
     cell == '#'
 }
 
-fn is_footprint(cell: char) -> bool {
-    cell == 'X'
-}
-
-fn is_within(it: i32, from: i32, to: i32) -> bool {
-    from <= it && it < to
-}
-
 fn get_original_footprint_indexes(
-    mut footprints: Vec<Vec<char>>,
-    mut position: (i32, i32),
-    n: i32,
-    m: i32,
-) -> Result<Vec<(usize, usize)>, Error> {
-    loop {
-        let (i, j) = position;
-        let direction = footprints[i as usize][j as usize];
-        let (di, dj) = get_delta_from_direction(direction)?;
-
-        let (ni, nj) = (i + di, j + dj);
-
-        if !is_within(ni, 0, n) || !is_within(nj, 0, m) {
-            break;
-        }
-        let next_cell = footprints[ni as usize][nj as usize];
-        if is_obstacle(next_cell) {
-            footprints[i as usize][j as usize] = get_next_direction(direction)?;
+    room: &Grid<char>,
+    (mut position, mut direction): (Pos, Dir4),
+) -> Vec<Pos> {
+    let mut footprints: HashSet<Pos> = HashSet::from([position]);
+
+    while let Some(next) = room.step(position, direction) {
+        if is_obstacle(room[next]) {
+            direction = direction.turn_right();
         } else {
-            footprints[i as usize][j as usize] = 'X';
-            footprints[ni as usize][nj as usize] = direction;
-            position = (ni, nj);
+            footprints.insert(next);
+            position = next;
         }
     }
 
-    Ok(footprints
-        .into_iter()
-        .enumerate()
-        .flat_map(|(i, line)| {
-            line.into_iter()
-                .enumerate()
-                .filter(|(_, cell)| is_guard(*cell) || is_footprint(*cell))
-                .map(|(j, _)| (i, j))
-                .collect::<Vec<(usize, usize)>>()
-        })
-        .collect())
+    footprints.into_iter().collect()
 }
 
-fn part_one() -> Result<(), Error> {
-    let room = read_room_from_file("./src/day_06/input.txt")?;
-
-    let n = room.len() as i32;
-    let m = room.get(0).context("empty room")?.len() as i32;
+fn is_infinite_loop(room: &Grid<char>, (mut position, mut direction): (Pos, Dir4)) -> bool {
+    let mut history: HashSet<(Pos, Dir4)> = HashSet::new();
 
-    let mut position: (i32, i32) = (-1, -1);
-
-    for i in 0..n {
-        for j in 0..m {
-            if is_guard(room[i as usize][j as usize]) {
-                position = (i, j);
-                break;
-            }
-        }
-        if position != (-1, -1) {
-            break;
+    while history.insert((position, direction)) {
+        let Some(next) = room.step(position, direction) else {
+            return false;
+        };
+        if is_obstacle(room[next]) {
+            direction = direction.turn_right();
+        } else {
+            position = next;
         }
     }
 
-    if position == (-1, -1) {
-        return Err(Error::msg("no guard found"));
-    }
-
-    println!(
-        "part one result {}",
-        get_original_footprint_indexes(room.clone(), position, n, m)?.len()
-    );
-
-    Ok(())
+    true
 }
 
-fn part_two() -> Result<(), Error> {
-    let room = read_room_from_file("./src/day_06/input.txt")?;
-
-    let n = room.len() as i32;
-    let m = room.get(0).context("empty room")?.len() as i32;
+pub struct Day06;
 
-    let mut position: (i32, i32) = (-1, -1);
+impl Solution for Day06 {
+    type Input = Grid<char>;
+    type Params = ();
 
-    for i in 0..n {
-        for j in 0..m {
-            if is_guard(room[i as usize][j as usize]) {
-                position = (i, j);
-                break;
-            }
-        }
-        if position != (-1, -1) {
-            break;
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_room(input)
     }
 
-    if position == (-1, -1) {
-        return Err(Error::msg("no guard found"));
-    }
+    fn part_one(room: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let guard = find_guard(room)?;
 
-    fn is_infinite_loop(
-        footprints: &mut Vec<Vec<char>>,
-        mut position: (i32, i32),
-        n: i32,
-        m: i32,
-    ) -> Result<bool, Error> {
-        let mut all_changes: HashMap<(i32, i32), char> = HashMap::new();
-        let mut history: HashSet<(i32, i32, char)> = HashSet::new();
-        let mut is_infinite = false;
-        loop {
-            let (i, j) = position;
-            let direction = footprints[i as usize][j as usize];
-            let (di, dj) = get_delta_from_direction(direction)?;
-
-            if history.contains(&(i, j, direction)) {
-                is_infinite = true;
-                break;
-            }
+        Ok(get_original_footprint_indexes(room, guard).len().into())
+    }
 
-            history.insert((i, j, direction));
+    fn part_two(room: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let guard = find_guard(room)?;
 
-            let (ni, nj) = (i + di, j + dj);
+        let mut result = 0;
+        let mut footprints = room.clone();
 
-            if !is_within(ni, 0, n) || !is_within(nj, 0, m) {
-                break;
+        for pos in get_original_footprint_indexes(room, guard) {
+            let cell = room[pos];
+            if pos == guard.0 || is_obstacle(cell) {
+                continue;
             }
-            let next_cell = footprints[ni as usize][nj as usize];
-            if is_obstacle(next_cell) {
-                if !all_changes.contains_key(&(i, j)) {
-                    all_changes.insert((i, j), footprints[i as usize][j as usize]);
-                }
-                footprints[i as usize][j as usize] = get_next_direction(direction)?;
-            } else {
-                if !all_changes.contains_key(&(i, j)) {
-                    all_changes.insert((i, j), footprints[i as usize][j as usize]);
-                }
-                if !all_changes.contains_key(&(ni, nj)) {
-                    all_changes.insert((ni, nj), footprints[ni as usize][nj as usize]);
-                }
-                footprints[i as usize][j as usize] = 'X';
-                footprints[ni as usize][nj as usize] = direction;
-                position = (ni, nj);
+            footprints[pos] = '#';
+            if is_infinite_loop(&footprints, guard) {
+                result += 1;
             }
+            footprints[pos] = cell;
         }
-        all_changes
-            .into_iter()
-            .for_each(|((i, j), cell)| footprints[i as usize][j as usize] = cell);
-        Ok(is_infinite)
-    }
 
-    let mut result = 0;
-    let mut footprints = room.clone();
-
-    let indexes = get_original_footprint_indexes(room.clone(), position, n, m)?;
-
-    for (i, j) in indexes {
-        let cell = room[i][j];
-        if is_guard(cell) || is_obstacle(cell) {
-            continue;
-        }
-        footprints[i][j] = '#';
-        if is_infinite_loop(&mut footprints, position, n, m)? {
-            result += 1;
-        }
-        footprints[i][j] = cell;
+        Ok(result.into())
     }
-
-    println!("part two result {}", result);
-
-    Ok(())
 }
 
-pub fn solve() -> Result<(), Error> {
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part_one(&input, &()).unwrap(), Answer::from(41));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part_two(&input, &()).unwrap(), Answer::from(6));
+    }
 }
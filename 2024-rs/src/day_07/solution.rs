@@ -1,20 +1,19 @@
 use anyhow::{Context, Error, Result};
-use std::fs::read_to_string;
 
+use crate::puzzle::{Answer, Solution};
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     result: i64,
     operands: Vec<i64>,
 }
 
-fn read_equations_from_file(file_path: &str) -> Result<Vec<Equation>, Error> {
-    let input = read_to_string(file_path).context("could not read file")?;
+fn read_equations(input: &str) -> Result<Vec<Equation>, Error> {
     let equations: Result<_> = input
         .lines()
         .map(|line| {
             let parts: Vec<&str> = line.split(": ").collect();
             let result = parts
-                .get(0)
+                .first()
                 .context("could not get result part")?
                 .parse::<i64>()
                 .context("could not convert result part to i64")?;
@@ -35,7 +34,7 @@ fn read_equations_from_file(file_path: &str) -> Result<Vec<Equation>, Error> {
         })
         .collect();
 
-    Ok(equations?)
+    equations
 }
 
 fn check_if_equation_is_true(equation: &Equation) -> bool {
@@ -114,34 +113,62 @@ fn check_if_equation_is_true_with_combine(equation: &Equation) -> bool {
     })
 }
 
-fn part_one() -> Result<(), Error> {
-    let equations = read_equations_from_file("./src/day_07/input.txt")?;
+pub struct Day07;
 
-    let result: i64 = equations
-        .into_iter()
-        .filter(check_if_equation_is_true)
-        .map(|eq| eq.result)
-        .sum();
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+    type Params = ();
 
-    println!("part one result {}", result);
-
-    Ok(())
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_equations(input)
+    }
 
-fn part_two() -> Result<(), Error> {
-    let equations = read_equations_from_file("./src/day_07/input.txt")?;
+    fn part_one(equations: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let result: i64 = equations
+            .iter()
+            .filter(|equation| check_if_equation_is_true(equation))
+            .map(|eq| eq.result)
+            .sum();
 
-    let result: i64 = equations
-        .into_iter()
-        .filter(check_if_equation_is_true_with_combine)
-        .map(|eq| eq.result)
-        .sum();
+        Ok(result.into())
+    }
 
-    println!("part two result {}", result);
+    fn part_two(equations: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let result: i64 = equations
+            .iter()
+            .filter(|equation| check_if_equation_is_true_with_combine(equation))
+            .map(|eq| eq.result)
+            .sum();
 
-    Ok(())
+        Ok(result.into())
+    }
 }
 
-pub fn solve() -> Result<(), Error> {
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part_one(&input, &()).unwrap(), Answer::from(3749));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+        assert_eq!(Day07::part_two(&input, &()).unwrap(), Answer::from(11387));
+    }
 }
@@ -1,111 +1,116 @@
-use anyhow::{Context, Result};
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    grid::{Grid, Pos},
+    puzzle::{Answer, Solution},
 };
 
-fn read_table_from_file(file_path: &str) -> Result<Vec<Vec<char>>> {
-    let input = read_to_string(file_path).context("could not read file")?;
-    let table = input.lines().map(|line| line.chars().collect()).collect();
-    Ok(table)
+fn read_table(input: &str) -> Result<Grid<char>> {
+    Grid::parse(input, Ok)
 }
 
-fn part_one() -> Result<()> {
-    let table = read_table_from_file("./src/day_08/input.txt")?;
-
-    let n = table.len() as i32;
-    let m = table.get(1).context("could not get the first row")?.len() as i32;
-
-    let mut chars: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
-
-    table.iter().enumerate().for_each(|(i, row)| {
-        row.iter()
-            .enumerate()
-            .filter(|(_, char)| char.is_ascii_alphanumeric())
-            .for_each(|(j, char)| {
-                chars
-                    .entry(*char)
-                    .or_insert(vec![])
-                    .push((i as i32, j as i32))
-            })
-    });
-
-    let mut antinodes: HashSet<(i32, i32)> = HashSet::new();
-
-    chars.iter().for_each(|(_, positions)| {
-        positions.iter().for_each(|&first| {
-            positions.iter().for_each(|&second| {
-                let (dx, dy) = (first.0 - second.0, first.1 - second.1);
-                if dx == 0 && dy == 0 {
-                    return;
-                }
-                let (ni, nj) = (first.0 + dx, first.1 + dy);
-                if ni < 0 || ni >= n || nj < 0 || nj >= m {
-                    return;
-                }
-                antinodes.insert((ni, nj));
-            })
-        });
-    });
+fn get_antennas(table: &Grid<char>) -> HashMap<char, Vec<Pos>> {
+    let mut chars: HashMap<char, Vec<Pos>> = HashMap::new();
 
-    println!("part one result {}", antinodes.len());
+    table
+        .iter()
+        .filter(|(_, char)| char.is_ascii_alphanumeric())
+        .for_each(|(pos, char)| chars.entry(*char).or_default().push(pos));
+
+    chars
+}
 
-    Ok(())
+fn get_delta(first: Pos, second: Pos) -> (isize, isize) {
+    (
+        first.0 as isize - second.0 as isize,
+        first.1 as isize - second.1 as isize,
+    )
 }
 
-fn part_two() -> Result<()> {
-    let table = read_table_from_file("./src/day_08/input.txt")?;
-
-    let n = table.len() as i32;
-    let m = table.get(1).context("could not get the first row")?.len() as i32;
-
-    let mut chars: HashMap<char, Vec<(i32, i32)>> = HashMap::new();
-
-    table.iter().enumerate().for_each(|(i, row)| {
-        row.iter()
-            .enumerate()
-            .filter(|(_, char)| char.is_ascii_alphanumeric())
-            .for_each(|(j, char)| {
-                chars
-                    .entry(*char)
-                    .or_insert(vec![])
-                    .push((i as i32, j as i32))
-            })
-    });
-
-    let mut antinodes: HashSet<(i32, i32)> = HashSet::new();
-
-    chars.iter().for_each(|(_, positions)| {
-        positions.iter().for_each(|&first| {
-            // insert itself
-            antinodes.insert(first);
-
-            // loop through all produced antinodes with other chars
-            positions.iter().for_each(|&second| {
-                let (dx, dy) = (first.0 - second.0, first.1 - second.1);
-                if dx == 0 && dy == 0 {
-                    return;
-                }
-
-                let mut ni = first.0 + dx;
-                let mut nj = first.1 + dy;
-                loop {
-                    if ni < 0 || ni >= n || nj < 0 || nj >= m {
-                        break;
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Grid<char>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_table(input)
+    }
+
+    fn part_one(table: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut antinodes: HashSet<Pos> = HashSet::new();
+
+        get_antennas(table).values().for_each(|positions| {
+            positions.iter().for_each(|&first| {
+                positions.iter().for_each(|&second| {
+                    if first == second {
+                        return;
+                    }
+                    if let Some(antinode) = table.offset(first, get_delta(first, second)) {
+                        antinodes.insert(antinode);
                     }
-                    antinodes.insert((ni, nj));
-                    ni += dx;
-                    nj += dy;
-                }
-            })
+                })
+            });
         });
-    });
 
-    println!("part two result {}", antinodes.len());
+        Ok(antinodes.len().into())
+    }
+
+    fn part_two(table: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut antinodes: HashSet<Pos> = HashSet::new();
+
+        get_antennas(table).values().for_each(|positions| {
+            positions.iter().for_each(|&first| {
+                // insert itself
+                antinodes.insert(first);
+
+                // loop through all produced antinodes with other chars
+                positions.iter().for_each(|&second| {
+                    if first == second {
+                        return;
+                    }
+                    table
+                        .ray(first, get_delta(first, second))
+                        .for_each(|antinode| {
+                            antinodes.insert(antinode);
+                        });
+                })
+            });
+        });
 
-    Ok(())
+        Ok(antinodes.len().into())
+    }
 }
 
-pub fn solve() -> Result<()> {
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part_one(&input, &()).unwrap(), Answer::from(14));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part_two(&input, &()).unwrap(), Answer::from(34));
+    }
 }
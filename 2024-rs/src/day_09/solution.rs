@@ -1,9 +1,10 @@
-use std::{fs::read_to_string, iter::repeat};
+use std::iter::repeat_n;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 
-fn get_disk_map() -> Result<Vec<i32>> {
-    let input = read_to_string("./src/day_09/input.txt").context("could not read file")?;
+use crate::puzzle::{Answer, Solution};
+
+fn get_disk_map(input: &str) -> Result<Vec<i32>> {
     input
         .trim()
         .chars()
@@ -12,113 +13,150 @@ fn get_disk_map() -> Result<Vec<i32>> {
         .collect()
 }
 
-fn get_disk_blocks() -> Result<Vec<i32>> {
-    let disk_map = get_disk_map()?;
+fn get_disk_blocks(input: &str) -> Result<Vec<i32>> {
+    let disk_map = get_disk_map(input)?;
     let mut blocks: Vec<i32> = vec![];
 
     disk_map.iter().enumerate().for_each(|(i, &num)| {
-        repeat(if i % 2 == 0 { i as i32 / 2 } else { -1 })
-            .take(num as usize)
+        repeat_n(if i % 2 == 0 { i as i32 / 2 } else { -1 }, num as usize)
             .for_each(|block| blocks.push(block))
     });
 
     Ok(blocks)
 }
 
-fn part_one() -> Result<()> {
-    let mut blocks = get_disk_blocks()?;
+pub struct Day09;
 
-    let mut left = 0usize;
-    let mut right = blocks.len() - 1;
+impl Solution for Day09 {
+    type Input = Vec<i32>;
+    type Params = ();
 
-    loop {
-        while left < right && blocks[left] != -1 {
-            left += 1;
-        }
-        while left < right && blocks[right] == -1 {
-            right -= 1;
-        }
-        if left >= right {
-            break;
-        }
-        (blocks[left], blocks[right]) = (blocks[right], blocks[left]);
+    fn parse(input: &str) -> Result<Self::Input> {
+        get_disk_blocks(input)
     }
 
-    let result: i64 = blocks
-        .iter()
-        .enumerate()
-        .map(|(i, &val)| i as i64 * val as i64)
-        .filter(|&val| val > 0)
-        .sum();
-
-    println!("part one result {}", result);
-
-    Ok(())
-}
-
-fn part_two() -> Result<()> {
-    let mut blocks = get_disk_blocks()?;
-
-    let mut id = *blocks
-        .iter()
-        .max()
-        .context("could not get the max element")?;
-
-    while id > 0 {
-        let count_current_id = blocks.iter().filter(|&block| *block == id).count() as i32;
-        let mut cur_group_size: i32 = 1;
-        let mut position: i32 = -1;
+    fn part_one(blocks: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        if blocks.is_empty() {
+            bail!("empty disk map");
+        }
+        let mut blocks = blocks.clone();
+        let mut left = 0usize;
+        let mut right = blocks.len() - 1;
 
-        for i in 1..blocks.len() {
-            if blocks[i - 1] == blocks[i] {
-                cur_group_size += 1;
-                continue;
+        loop {
+            while left < right && blocks[left] != -1 {
+                left += 1;
             }
-            if cur_group_size >= count_current_id && blocks[i - 1] == -1 {
-                position = i as i32 - cur_group_size;
+            while left < right && blocks[right] == -1 {
+                right -= 1;
+            }
+            if left >= right {
                 break;
             }
-            cur_group_size = 1;
+            (blocks[left], blocks[right]) = (blocks[right], blocks[left]);
         }
 
-        if position == -1 && cur_group_size >= count_current_id && blocks[blocks.len() - 1] == -1 {
-            position = blocks.len() as i32 - cur_group_size;
-        }
-        if position == -1 {
-            id -= 1;
-            continue;
-        }
+        let result: i64 = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, &val)| i as i64 * val as i64)
+            .filter(|&val| val > 0)
+            .sum();
+
+        Ok(result.into())
+    }
 
-        if let Some((first, _)) = blocks.iter().enumerate().find(|&(_, &block)| block == id) {
-            if first < position as usize {
+    fn part_two(blocks: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut blocks = blocks.clone();
+        let mut id = *blocks
+            .iter()
+            .max()
+            .context("could not get the max element")?;
+
+        while id > 0 {
+            let count_current_id = blocks.iter().filter(|&block| *block == id).count() as i32;
+            let mut cur_group_size: i32 = 1;
+            let mut position: i32 = -1;
+
+            for i in 1..blocks.len() {
+                if blocks[i - 1] == blocks[i] {
+                    cur_group_size += 1;
+                    continue;
+                }
+                if cur_group_size >= count_current_id && blocks[i - 1] == -1 {
+                    position = i as i32 - cur_group_size;
+                    break;
+                }
+                cur_group_size = 1;
+            }
+
+            if position == -1
+                && cur_group_size >= count_current_id
+                && blocks[blocks.len() - 1] == -1
+            {
+                position = blocks.len() as i32 - cur_group_size;
+            }
+            if position == -1 {
                 id -= 1;
                 continue;
             }
-            for i in 0..blocks.len() {
-                if blocks[i] == id {
-                    blocks[i] = -1;
+
+            if let Some((first, _)) = blocks.iter().enumerate().find(|&(_, &block)| block == id) {
+                if first < position as usize {
+                    id -= 1;
+                    continue;
+                }
+                for i in 0..blocks.len() {
+                    if blocks[i] == id {
+                        blocks[i] = -1;
+                    }
+                }
+                for i in position..position + count_current_id {
+                    blocks[i as usize] = id;
                 }
             }
-            for i in position..position + count_current_id {
-                blocks[i as usize] = id;
-            }
+
+            id -= 1;
         }
 
-        id -= 1;
+        let result: i64 = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, &val)| i as i64 * val as i64)
+            .filter(|&val| val > 0)
+            .sum();
+
+        Ok(result.into())
     }
+}
 
-    let result: i64 = blocks
-        .iter()
-        .enumerate()
-        .map(|(i, &val)| i as i64 * val as i64)
-        .filter(|&val| val > 0)
-        .sum();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    println!("part two result {}", result);
+    const EXAMPLE: &str = "\
+2333133121414131402
+";
 
-    Ok(())
-}
+    #[test]
+    fn part_one_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part_one(&input, &()).unwrap(), Answer::from(1928));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+        assert_eq!(Day09::part_two(&input, &()).unwrap(), Answer::from(2858));
+    }
 
-pub fn solve() -> Result<()> {
-    part_two()
+    #[test]
+    fn empty_disk_maps_are_errors() {
+        let input = Day09::parse("").unwrap();
+        assert_eq!(
+            Day09::part_one(&input, &()).unwrap_err().to_string(),
+            "empty disk map"
+        );
+        assert!(Day09::part_two(&input, &()).is_err());
+    }
 }
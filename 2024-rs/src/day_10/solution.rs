@@ -1,130 +1,115 @@
-use std::{collections::HashSet, fs::read_to_string, iter::repeat};
+use std::collections::HashSet;
 
 use anyhow::{Context, Result};
 
-fn get_map_from_input() -> Result<Vec<Vec<i32>>> {
-    let input = read_to_string("./src/day_10/input.txt").context("could not read file")?;
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|char| {
-                    let val = char.to_digit(10).context("could not convert to digit")?;
-                    Ok(val as i32)
-                })
-                .collect()
-        })
-        .collect()
+use crate::{
+    grid::{Grid, Pos},
+    puzzle::{Answer, Solution},
+};
+
+fn get_map_from_input(input: &str) -> Result<Grid<i32>> {
+    Grid::parse(input, |char| {
+        let val = char.to_digit(10).context("could not convert to digit")?;
+        Ok(val as i32)
+    })
 }
 
-fn get_positions_with_value(value: i32, map: &Vec<Vec<i32>>) -> Vec<(usize, usize)> {
+fn get_positions_with_value(value: i32, map: &Grid<i32>) -> Vec<Pos> {
     map.iter()
-        .enumerate()
-        .flat_map(|(i, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, &cell)| cell == value)
-                .map(|(j, _)| (i, j))
-                .collect::<Vec<(usize, usize)>>()
-        })
+        .filter(|(_, &cell)| cell == value)
+        .map(|(pos, _)| pos)
         .collect()
 }
 
-fn part_one() -> Result<()> {
-    let map = get_map_from_input()?;
-    let n = map.len() as i32;
-    let m = map
-        .get(0)
-        .context("could not get the first item in map")?
-        .len() as i32;
-
-    let mut result: Vec<Vec<HashSet<(usize, usize)>>> =
-        repeat(repeat(HashSet::new()).take(m as usize).collect())
-            .take(n as usize)
-            .collect();
-
-    get_positions_with_value(9, &map)
-        .iter()
-        .for_each(|&(i, j)| {
-            result[i][j].insert((i, j));
-        });
+fn get_step_neighbours(pos: Pos, map: &Grid<i32>) -> Vec<Pos> {
+    map.neighbours4(pos)
+        .filter(|&neighbour| map[neighbour] == map[pos] + 1)
+        .collect()
+}
 
-    let dxdy: Vec<(i32, i32)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
+pub struct Day10;
 
-    (0..9).rev().for_each(|step| {
-        get_positions_with_value(step, &map)
-            .iter()
-            .for_each(|&(i, j)| {
-                let step_neighbours: Vec<(usize, usize)> = dxdy
-                    .iter()
-                    .map(|&(dx, dy)| (i as i32 + dx, j as i32 + dy))
-                    .filter(|&(ni, nj)| 0 <= ni && ni < n && 0 <= nj && nj < m)
-                    .map(|(ni, nj)| (ni as usize, nj as usize))
-                    .filter(|&(ni, nj)| map[ni][nj] == step + 1)
-                    .collect();
-                step_neighbours.iter().for_each(|&(ni, nj)| {
-                    for pos in result[ni][nj].clone() {
-                        result[i][j].insert(pos);
-                    }
-                });
-            })
-    });
+impl Solution for Day10 {
+    type Input = Grid<i32>;
+    type Params = ();
 
-    let result: i32 = get_positions_with_value(0, &map)
-        .iter()
-        .map(|&(i, j)| result[i][j].len() as i32)
-        .sum();
+    fn parse(input: &str) -> Result<Self::Input> {
+        get_map_from_input(input)
+    }
 
-    println!("part one result {}", result);
+    fn part_one(map: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut result: Grid<HashSet<Pos>> = Grid::new(map.width(), map.height(), HashSet::new());
 
-    Ok(())
-}
+        get_positions_with_value(9, map).iter().for_each(|&pos| {
+            result[pos].insert(pos);
+        });
 
-fn part_two() -> Result<()> {
-    let map = get_map_from_input()?;
-    let n = map.len() as i32;
-    let m = map
-        .get(0)
-        .context("could not get the first item in map")?
-        .len() as i32;
+        (0..9).rev().for_each(|step| {
+            get_positions_with_value(step, map).iter().for_each(|&pos| {
+                get_step_neighbours(pos, map).iter().for_each(|&neighbour| {
+                    for reachable in result[neighbour].clone() {
+                        result[pos].insert(reachable);
+                    }
+                });
+            })
+        });
 
-    let mut result: Vec<Vec<i32>> = repeat(repeat(0).take(m as usize).collect())
-        .take(n as usize)
-        .collect();
+        let result: i32 = get_positions_with_value(0, map)
+            .iter()
+            .map(|&pos| result[pos].len() as i32)
+            .sum();
 
-    get_positions_with_value(9, &map)
-        .iter()
-        .for_each(|&(i, j)| result[i][j] = 1);
+        Ok(result.into())
+    }
 
-    let dxdy: Vec<(i32, i32)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
+    fn part_two(map: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut result: Grid<i32> = Grid::new(map.width(), map.height(), 0);
 
-    (0..9).rev().for_each(|step| {
-        get_positions_with_value(step, &map)
+        get_positions_with_value(9, map)
             .iter()
-            .for_each(|&(i, j)| {
-                let step_neighbours: Vec<(usize, usize)> = dxdy
-                    .iter()
-                    .map(|&(dx, dy)| (i as i32 + dx, j as i32 + dy))
-                    .filter(|&(ni, nj)| 0 <= ni && ni < n && 0 <= nj && nj < m)
-                    .map(|(ni, nj)| (ni as usize, nj as usize))
-                    .filter(|&(ni, nj)| map[ni][nj] == step + 1)
-                    .collect();
-                step_neighbours.iter().for_each(|&(ni, nj)| {
-                    result[i][j] += result[ni][nj];
+            .for_each(|&pos| result[pos] = 1);
+
+        (0..9).rev().for_each(|step| {
+            get_positions_with_value(step, map).iter().for_each(|&pos| {
+                get_step_neighbours(pos, map).iter().for_each(|&neighbour| {
+                    result[pos] += result[neighbour];
                 });
             })
-    });
-
-    let result: i32 = get_positions_with_value(0, &map)
-        .iter()
-        .map(|&(i, j)| result[i][j])
-        .sum();
+        });
 
-    println!("part two result {}", result);
+        let result: i32 = get_positions_with_value(0, map)
+            .iter()
+            .map(|&pos| result[pos])
+            .sum();
 
-    Ok(())
+        Ok(result.into())
+    }
 }
 
-pub fn solve() -> Result<()> {
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part_one(&input, &()).unwrap(), Answer::from(36));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part_two(&input, &()).unwrap(), Answer::from(81));
+    }
 }
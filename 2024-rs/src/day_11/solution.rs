@@ -1,14 +1,23 @@
-use std::{collections::HashMap, fs::read_to_string, i128, iter::repeat};
+use std::iter::repeat_n;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Result};
 
-fn get_initial_stones() -> Result<Vec<String>> {
-    let input = read_to_string("./src/day_11/input.txt").context("could not read file")?;
-    Ok(input
+use crate::{
+    memo::Memo,
+    puzzle::{Answer, Solution},
+};
+
+fn get_initial_stones(input: &str) -> Result<Vec<String>> {
+    input
         .trim()
         .split(" ")
-        .map(|stone| stone.to_string())
-        .collect())
+        .map(|stone| {
+            if stone.is_empty() || !stone.chars().all(|c| c.is_ascii_digit()) {
+                bail!("stone '{}' is not a number", stone);
+            }
+            Ok(stone.to_string())
+        })
+        .collect()
 }
 
 fn without_leading_zeroes(num_str: &str) -> &str {
@@ -17,24 +26,25 @@ fn without_leading_zeroes(num_str: &str) -> &str {
     while i + 1 < chars.len() && chars[i] == '0' {
         i += 1;
     }
-    num_str.get(i..).unwrap()
+    &num_str[i..]
 }
 
 fn transform_stone(stone: &String) -> Vec<String> {
-    if *stone == String::from("0") {
+    if stone == "0" {
         vec![String::from("1")]
-    } else if stone.len() % 2 == 0 {
+    } else if stone.len().is_multiple_of(2) {
         vec![
-            stone.get(0..stone.len() / 2).unwrap().to_string(),
-            without_leading_zeroes(stone.get(stone.len() / 2..).unwrap()).to_string(),
+            stone[..stone.len() / 2].to_string(),
+            without_leading_zeroes(&stone[stone.len() / 2..]).to_string(),
         ]
     } else {
-        vec![(stone.parse::<i128>().unwrap() * 2024).to_string()]
+        let stone: i128 = stone.parse().expect("stones are checked to be numbers");
+        vec![(stone * 2024).to_string()]
     }
 }
 
-fn part_one(blinks_count: usize) -> Result<()> {
-    let mut stones = get_initial_stones()?;
+fn count_stones_by_blinking(stones: &[String], blinks_count: usize) -> usize {
+    let mut stones = stones.to_vec();
 
     fn blink(stones: &mut Vec<String>) {
         let mut next: Vec<String> = vec![];
@@ -47,57 +57,80 @@ fn part_one(blinks_count: usize) -> Result<()> {
         *stones = next;
     }
 
-    repeat(0)
-        .take(blinks_count)
-        .for_each(|_| blink(&mut stones));
-
-    println!("result after {} blinks:\n{}", blinks_count, stones.len());
+    repeat_n(0, blinks_count).for_each(|_| blink(&mut stones));
 
-    Ok(())
+    stones.len()
 }
 
-fn part_two(blinks_count: i32) -> Result<()> {
+fn count_stones_with_cache(stones: &[String], blinks_count: i32) -> i128 {
     fn count_after_blink(
         stone: &String,
         count: i32,
-        cache: &mut HashMap<String, HashMap<i32, i128>>,
+        cache: &mut Memo<(String, i32), i128>,
     ) -> i128 {
         if count == 0 {
             return 1;
         }
-        if let Some(top) = cache.get(stone) {
-            if let Some(result) = top.get(&count) {
-                return *result;
-            }
-        }
-        let next = transform_stone(stone);
-        let result: i128 = next
-            .iter()
-            .map(|next_stone| count_after_blink(next_stone, count - 1, cache))
-            .sum();
-        cache
-            .entry(stone.clone())
-            .or_insert(HashMap::new())
-            .insert(count, result);
-        result
+        cache.get_or_insert_with((stone.clone(), count), |cache| {
+            transform_stone(stone)
+                .iter()
+                .map(|next_stone| count_after_blink(next_stone, count - 1, cache))
+                .sum()
+        })
     }
 
-    let stones = get_initial_stones()?;
-    let mut cache: HashMap<String, HashMap<i32, i128>> = HashMap::new();
+    let mut cache = Memo::new();
 
-    let result: i128 = stones
+    stones
         .iter()
         .map(|stone| count_after_blink(stone, blinks_count, &mut cache))
-        .sum();
+        .sum()
+}
+
+pub struct Day11;
 
-    println!("result after {} blinks:\n{}", blinks_count, result);
+impl Solution for Day11 {
+    type Input = Vec<String>;
+    type Params = ();
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        get_initial_stones(input)
+    }
+
+    fn part_one(stones: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        Ok(count_stones_by_blinking(stones, 25).into())
+    }
+
+    fn part_two(stones: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        Ok(count_stones_with_cache(stones, 75).into())
+    }
 }
 
-pub fn solve() -> Result<()> {
-    let blinks_count = 75;
-    // let _ = part_one(blinks_count as usize)?;
-    let _ = part_two(blinks_count)?;
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+125 17
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_one(&input, &()).unwrap(), Answer::from(55312));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day11::part_two(&input, &()).unwrap(),
+            Answer::from(65601038650482_i64)
+        );
+    }
+
+    #[test]
+    fn rejects_stones_that_are_not_numbers() {
+        assert!(Day11::parse("125 x7").is_err());
+    }
 }
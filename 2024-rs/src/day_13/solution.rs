@@ -1,143 +1,145 @@
-use std::fs::read_to_string;
+use anyhow::{anyhow, Context, Result};
 
-use anyhow::{Context, Result};
-use regex::Regex;
+use crate::{
+    math::solve_linear,
+    parse::{self, ParseResult},
+    puzzle::{Answer, Solution},
+};
 
 #[derive(Debug, Clone)]
-struct Game {
+pub struct Game {
     buttons: Vec<(i64, i64)>,
     prize: (i64, i64),
 }
 
-fn read_games_input() -> Result<Vec<Game>> {
-    let input = read_to_string("./src/day_13/input.txt").context("could not read file")?;
-
-    let button_re = Regex::new(r"Button (A|B)\: X\+(?<x>\d+)\, Y\+(?<y>\d+)")?;
-    let prize_re = Regex::new(r"Prize\: X\=(?<x>\d+)\, Y\=(?<y>\d+)")?;
-
-    input
-        .split("\n\n")
-        .map(|game_input| {
-            let buttons = button_re
-                .captures_iter(game_input)
-                .map(|capture| {
-                    let x: i64 = capture
-                        .name("x")
-                        .context("x value not found")?
-                        .as_str()
-                        .parse()?;
-                    let y: i64 = capture
-                        .name("y")
-                        .context("y value not found")?
-                        .as_str()
-                        .parse()?;
-                    Ok((x, y))
-                })
-                .collect::<Result<Vec<(i64, i64)>>>()?;
-            let prize_capture = prize_re
-                .captures(game_input)
-                .context("could not capture prize")?;
-            let prize_x: i64 = prize_capture
-                .name("x")
-                .context("x value not found in prize")?
-                .as_str()
-                .parse()?;
-            let prize_y: i64 = prize_capture
-                .name("y")
-                .context("y value not found in prize")?
-                .as_str()
-                .parse()?;
+fn read_games_input(input: &str) -> Result<Vec<Game>> {
+    parse::sections(input)
+        .iter()
+        .map(|section| {
+            let numbers = section
+                .lines()
+                .map(|line| line.ints_n::<i64, 2>().map(|[x, y]| (x, y)))
+                .collect::<ParseResult<Vec<(i64, i64)>>>()?;
+            let (prize, buttons) = numbers
+                .split_last()
+                .context(format!("empty game at line {}", section.line))?;
+            if buttons.len() != 2 {
+                return Err(anyhow!(
+                    "expected two buttons in the game at line {}, found {}",
+                    section.line,
+                    buttons.len()
+                ));
+            }
             Ok(Game {
-                buttons,
-                prize: (prize_x, prize_y),
+                buttons: buttons.to_vec(),
+                prize: *prize,
             })
         })
         .collect()
 }
 
-fn part_one() -> Result<()> {
-    let games = read_games_input()?;
-
-    let result: i64 = games
-        .into_iter()
-        .map(|game| {
-            let prize = game.prize;
-            let button_a = game.buttons[0];
-            let button_b = game.buttons[1];
-
-            let mut min_score: Option<i64> = None;
-            for a_count in 0..=100i64 {
-                for b_count in 0..=100i64 {
-                    let x = button_a.0 * a_count + button_b.0 * b_count;
-                    let y = button_a.1 * a_count + button_b.1 * b_count;
-                    if prize != (x, y) {
-                        continue;
-                    }
-
-                    let current_score = a_count * 3 + b_count;
-                    if min_score.is_none() {
-                        min_score = Some(current_score);
-                    } else {
-                        min_score = min_score.min(Some(current_score));
-                    }
-                }
-            }
-            min_score.unwrap_or(0)
-        })
-        .sum();
-
-    println!("part one result {}", result);
-
-    Ok(())
+fn solve_game_for_part_two(game: &Game) -> i128 {
+    let diff: i128 = 10000000000000;
+    let prize = (game.prize.0 as i128 + diff, game.prize.1 as i128 + diff);
+    let (button_a, button_b) = (game.buttons[0], game.buttons[1]);
+
+    let presses = solve_linear(
+        [
+            [button_a.0 as i128, button_b.0 as i128],
+            [button_a.1 as i128, button_b.1 as i128],
+        ],
+        [prize.0, prize.1],
+    );
+
+    match presses.map(|[k, l]| (k.to_integer(), l.to_integer())) {
+        Some((Some(k), Some(l))) if k >= 0 && l >= 0 => 3 * k + l,
+        _ => 0,
+    }
 }
 
-fn solve_game_for_part_two(game: &Game) -> i64 {
-    let diff: i64 = 10000000000000;
-    let prize = (game.prize.0 + diff, game.prize.1 + diff);
-    let button_a = game.buttons[0];
-    let button_b = game.buttons[1];
+pub struct Day13;
 
-    let upper_l = button_a.0 * prize.1 - button_a.1 * prize.0;
-    let lower_l = button_a.0 * button_b.1 - button_a.1 * button_b.0;
+impl Solution for Day13 {
+    type Input = Vec<Game>;
+    type Params = ();
 
-    if upper_l % lower_l != 0 {
-        return 0;
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_games_input(input)
     }
 
-    let l = upper_l / lower_l;
-
-    let upper_k = prize.0 - button_b.0 * l;
-    let lower_k = button_a.0;
+    fn part_one(games: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let result: i64 = games
+            .iter()
+            .map(|game| {
+                let prize = game.prize;
+                let button_a = game.buttons[0];
+                let button_b = game.buttons[1];
+
+                let mut min_score: Option<i64> = None;
+                for a_count in 0..=100i64 {
+                    for b_count in 0..=100i64 {
+                        let x = button_a.0 * a_count + button_b.0 * b_count;
+                        let y = button_a.1 * a_count + button_b.1 * b_count;
+                        if prize != (x, y) {
+                            continue;
+                        }
+
+                        let current_score = a_count * 3 + b_count;
+                        if min_score.is_none() {
+                            min_score = Some(current_score);
+                        } else {
+                            min_score = min_score.min(Some(current_score));
+                        }
+                    }
+                }
+                min_score.unwrap_or(0)
+            })
+            .sum();
 
-    if upper_k % lower_k != 0 {
-        return 0;
+        Ok(result.into())
     }
 
-    let k = upper_k / lower_k;
+    fn part_two(games: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let result: i128 = games.iter().map(solve_game_for_part_two).sum();
 
-    let x = button_a.0 * k + button_b.0 * l;
-    let y = button_a.1 * k + button_b.1 * l;
-
-    if prize != (x, y) {
-        return 0;
+        Ok(result.into())
     }
-
-    return 3 * k + l;
 }
 
-fn part_two() -> Result<()> {
-    let games = read_games_input()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let result: i64 = games
-        .into_iter()
-        .map(|game| solve_game_for_part_two(&game))
-        .sum();
+    const EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
 
-    println!("part two result {}", result);
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
 
-    Ok(())
-}
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
 
-pub fn solve() -> Result<()> {
-    part_two()
+    #[test]
+    fn part_one_example() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part_one(&input, &()).unwrap(), Answer::from(480));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day13::part_two(&input, &()).unwrap(),
+            Answer::from(875318608908_i64)
+        );
+    }
 }
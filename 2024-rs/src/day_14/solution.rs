@@ -1,35 +1,44 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs::read_to_string,
-    iter::repeat,
-};
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
 
-use anyhow::{Context, Result};
+use crate::{
+    grid::{Grid, Pos},
+    math::crt,
+    params::{parse_positive, parse_value, unknown_param, Params, Preset},
+    point::Point,
+    puzzle::{Answer, Solution},
+    region::{Connectivity, Regions},
+};
 
 #[derive(Debug)]
-struct Robot {
-    position: (i32, i32),
-    velocity: (i32, i32),
+pub struct Robot {
+    position: Point,
+    velocity: Point,
+}
+
+impl Robot {
+    fn position_after(&self, seconds: i64, width: i64, height: i64) -> Point {
+        (self.position + self.velocity * seconds).rem_euclid(width, height)
+    }
 }
 
-fn get_robots_data(file_name: &str) -> Result<Vec<Robot>> {
-    let input =
-        read_to_string(format!("./src/day_14/{}.txt", file_name)).context("could not read file")?;
+fn get_robots_data(input: &str) -> Result<Vec<Robot>> {
     input
         .lines()
         .map(|line| {
             let parts: Vec<_> = line.split(" ").collect();
             let p_part = parts
-                .get(0)
+                .first()
                 .context("could not get position part")?
                 .get(2..)
                 .context("could not get position data part")?
                 .split(",")
                 .map(|p| {
-                    p.parse::<i32>()
-                        .context(format!("could not parse p '{}' to i32", p))
+                    p.parse::<i64>()
+                        .context(format!("could not parse p '{}' to i64", p))
                 })
-                .collect::<Result<Vec<i32>>>()?;
+                .collect::<Result<Vec<i64>>>()?;
             let v_part = parts
                 .get(1)
                 .context("could not get velocity part")?
@@ -37,18 +46,18 @@ fn get_robots_data(file_name: &str) -> Result<Vec<Robot>> {
                 .context("could not get velocity data part")?
                 .split(",")
                 .map(|v| {
-                    v.parse::<i32>()
-                        .context(format!("could not parse v '{}' to i32", v))
+                    v.parse::<i64>()
+                        .context(format!("could not parse v '{}' to i64", v))
                 })
-                .collect::<Result<Vec<i32>>>()?;
+                .collect::<Result<Vec<i64>>>()?;
 
-            let position: (i32, i32) = (
-                *p_part.get(0).context("could not get first position")?,
+            let position = Point::new(
+                *p_part.first().context("could not get first position")?,
                 *p_part.get(1).context("could not get second position")?,
             );
 
-            let velocity: (i32, i32) = (
-                *v_part.get(0).context("could not get first velocity")?,
+            let velocity = Point::new(
+                *v_part.first().context("could not get first velocity")?,
                 *v_part.get(1).context("could not get second velocity")?,
             );
 
@@ -57,145 +66,233 @@ fn get_robots_data(file_name: &str) -> Result<Vec<Robot>> {
         .collect()
 }
 
-fn part_one() -> Result<()> {
-    let robots = get_robots_data("input")?;
-
-    let seconds = 100;
-    // example dimensions
-    // let (n, m) = (11, 7);
-    // main dimensions
-    let (n, m) = (101, 103);
-
-    let end_positions: Vec<(i32, i32)> = robots
-        .into_iter()
-        .map(|robot| {
-            let end_x = (robot.position.0 + robot.velocity.0 * seconds).rem_euclid(n);
-            let end_y = (robot.position.1 + robot.velocity.1 * seconds).rem_euclid(m);
-            (end_x, end_y)
-        })
-        .collect();
-
-    let (mid_n, mid_m) = (n / 2, m / 2);
-    let mut quadrants: HashMap<(i32, i32), i32> = HashMap::new();
+fn check_tree(table: &Grid<bool>) -> bool {
+    Regions::matching(table, Connectivity::Four, |&robot| robot)
+        .largest()
+        .is_some_and(|island| island.area() >= 30)
+}
 
-    end_positions
-        .into_iter()
-        .filter(|&(x, y)| x != mid_n && y != mid_m)
-        .for_each(|(x, y)| {
-            *quadrants
-                .entry((if x < mid_n { 0 } else { 1 }, if y < mid_m { 0 } else { 1 }))
-                .or_insert(0) += 1
-        });
+/// How far apart the values are, `n` times their sum of squared deviations to stay in integers.
+fn spread(values: impl Iterator<Item = i64>) -> i128 {
+    let (n, sum, squares) = values.fold((0i128, 0i128, 0i128), |(n, sum, squares), v| {
+        (n + 1, sum + v as i128, squares + (v * v) as i128)
+    });
+    n * squares - sum * sum
+}
 
-    let result: i32 = quadrants.values().product();
+/// Columns repeat every `width` seconds and rows every `height` seconds, so the second where
+/// each is bunched up the most is combined with CRT and confirmed with [`check_tree`].
+fn tree_second_by_periods(robots: &[Robot], params: &Day14Params) -> Option<i64> {
+    let (n, m) = (params.width, params.height);
+    let tightest = |period: i64, axis: fn(Point) -> i64| {
+        (0..period).min_by_key(|&second| {
+            spread(
+                robots
+                    .iter()
+                    .map(|robot| axis(robot.position_after(second, n, m))),
+            )
+        })
+    };
+
+    let second_x = tightest(n, |point| point.x)?;
+    let second_y = tightest(m, |point| point.y)?;
+    let (second, _) = crt([(second_x as i128, n as i128), (second_y as i128, m as i128)])?;
+    let second = i64::try_from(second).ok()?;
+    if second >= params.max_seconds {
+        return None;
+    }
 
-    println!("part one result {}", result);
+    let mut table: Grid<bool> = Grid::new(n as usize, m as usize, false);
+    for robot in robots {
+        let pos = robot
+            .position_after(second, n, m)
+            .to_pos()
+            .expect("wrapped into the grid");
+        table[pos] = true;
+    }
+    check_tree(&table).then_some(second)
+}
 
-    Ok(())
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day14Params {
+    pub width: i64,
+    pub height: i64,
+    /// seconds simulated in part one
+    pub seconds: i64,
+    /// how many seconds part two searches for the tree
+    pub max_seconds: i64,
 }
 
-fn show_table(table: &Vec<Vec<bool>>) {
-    for row in table {
-        for cell in row {
-            print!("{}", if *cell { "#" } else { "." })
+impl Params for Day14Params {
+    fn preset(preset: Preset) -> Self {
+        let (width, height) = match preset {
+            Preset::Example => (11, 7),
+            Preset::Real => (101, 103),
+        };
+        Day14Params {
+            width,
+            height,
+            seconds: 100,
+            max_seconds: 10000,
         }
-        println!("");
     }
-}
-
-fn get_valid_neighbours((i, j): (usize, usize), (n, m): (usize, usize)) -> Vec<(usize, usize)> {
-    let dxdy: Vec<(i32, i32)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-    let mut valids = vec![];
 
-    for (dx, dy) in dxdy {
-        let ni = dx + i as i32;
-        let nj = dy + j as i32;
-        if ni < 0 || ni >= n as i32 || nj < 0 || nj >= m as i32 {
-            continue;
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "width" => self.width = parse_positive(key, value)?,
+            "height" => self.height = parse_positive(key, value)?,
+            "seconds" => self.seconds = parse_value(key, value)?,
+            "max_seconds" => self.max_seconds = parse_value(key, value)?,
+            _ => {
+                return Err(unknown_param(
+                    key,
+                    &["width", "height", "seconds", "max_seconds"],
+                ))
+            }
         }
-        valids.push((ni as usize, nj as usize));
+        Ok(())
     }
-
-    valids
 }
 
-fn check_tree(table: &Vec<Vec<bool>>, second: i32) -> bool {
-    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
-    let mut was: HashSet<(usize, usize)> = HashSet::new();
+pub struct Day14;
 
-    let n = table.len();
-    let m = table[0].len();
-    let mut max_island = 0;
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+    type Params = Day14Params;
 
-    for i in 0..n {
-        for j in 0..m {
-            if table[i][j] == false || was.contains(&(i, j)) {
-                continue;
-            }
-            let mut curr_count = 0;
-            queue.push_back((i, j));
-            while let Some(top) = queue.pop_front() {
-                curr_count += 1;
-                was.insert(top);
-
-                let neighours: Vec<(usize, usize)> = get_valid_neighbours(top, (n, m))
-                    .into_iter()
-                    .filter(|(x, y)| table[*x][*y] && !was.contains(&(*x, *y)))
-                    .collect();
-
-                neighours.into_iter().for_each(|(x, y)| {
-                    was.insert((x, y));
-                    queue.push_back((x, y));
-                });
-            }
-            max_island = max_island.max(curr_count);
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        get_robots_data(input)
     }
 
-    if max_island < 30 {
-        return false;
-    }
+    fn part_one(robots: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        let seconds = params.seconds;
+        let (n, m) = (params.width, params.height);
 
-    println!("Second: {}", second);
-    show_table(&table);
-    println!("");
-    true
-}
+        let end_positions: Vec<Point> = robots
+            .iter()
+            .map(|robot| robot.position_after(seconds, n, m))
+            .collect();
 
-fn part_two() -> Result<()> {
-    let robots = get_robots_data("input")?;
+        let (mid_n, mid_m) = (n / 2, m / 2);
+        let mut quadrants: HashMap<(i32, i32), i32> = HashMap::new();
 
-    // example dimensions
-    // let (n, m) = (11, 7);
-    // main dimensions
-    let (n, m) = (101, 103);
-    let mut table: Vec<Vec<bool>> = repeat(repeat(false).take(m).collect()).take(n).collect();
+        end_positions
+            .into_iter()
+            .filter(|&Point { x, y }| x != mid_n && y != mid_m)
+            .for_each(|Point { x, y }| {
+                *quadrants
+                    .entry((if x < mid_n { 0 } else { 1 }, if y < mid_m { 0 } else { 1 }))
+                    .or_insert(0) += 1
+            });
 
-    for second in 0..10000 {
-        println!("checking {}", second);
-        let end_positions: Vec<(usize, usize)> = robots
-            .iter()
-            .map(|robot| {
-                let end_x = (robot.position.0 + robot.velocity.0 * second).rem_euclid(n as i32);
-                let end_y = (robot.position.1 + robot.velocity.1 * second).rem_euclid(m as i32);
-                (end_x as usize, end_y as usize)
-            })
-            .collect();
+        let result: i32 = quadrants.values().product();
 
-        // set table
-        end_positions.iter().for_each(|&(x, y)| table[x][y] = true);
+        Ok(result.into())
+    }
 
-        if check_tree(&table, second) {
-            break;
+    fn part_two(robots: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        if let Some(second) = tree_second_by_periods(robots, params) {
+            return Ok(second.into());
         }
 
-        // cleanup table
-        end_positions.iter().for_each(|&(x, y)| table[x][y] = false);
-    }
+        // the periods did not point at a tree, look at every second instead
+        let (n, m) = (params.width, params.height);
+        let mut table: Grid<bool> = Grid::new(n as usize, m as usize, false);
+
+        for second in 0..params.max_seconds {
+            let end_positions: Vec<Pos> = robots
+                .iter()
+                .map(|robot| {
+                    robot
+                        .position_after(second, n, m)
+                        .to_pos()
+                        .expect("wrapped into the grid")
+                })
+                .collect();
+
+            // set table
+            end_positions.iter().for_each(|&pos| table[pos] = true);
+
+            if check_tree(&table) {
+                return Ok(second.into());
+            }
 
-    Ok(())
+            // cleanup table
+            end_positions.iter().for_each(|&pos| table[pos] = false);
+        }
+
+        Err(anyhow!("no christmas tree found"))
+    }
 }
 
-pub fn solve() -> Result<()> {
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day14::part_one(&input, &Day14Params::preset(Preset::Example)).unwrap(),
+            Answer::from(12)
+        );
+    }
+
+    #[test]
+    fn part_two_combines_the_periods() {
+        let params = Day14Params::preset(Preset::Real);
+        let (n, m) = (params.width, params.height);
+        let tree_second = 7412;
+
+        // pseudo random velocities, the first 100 robots form a 10x10 block at `tree_second`
+        let mut seed: i64 = 17;
+        let mut next = |modulo: i64| {
+            seed = (seed * 1103515245 + 12345) % 2147483648;
+            seed % modulo
+        };
+        let mut robots = vec![];
+        for i in 0..250 {
+            let velocity = Point::new(next(n * 2) - n, next(m * 2) - m);
+            let target = if i < 100 {
+                Point::new(40 + i % 10, 50 + i / 10)
+            } else {
+                Point::new(next(n), next(m))
+            };
+            let position = (target - velocity * tree_second).rem_euclid(n, m);
+            robots.push(Robot { position, velocity });
+        }
+
+        assert_eq!(tree_second_by_periods(&robots, &params), Some(tree_second));
+        assert_eq!(
+            Day14::part_two(&robots, &params).unwrap(),
+            Answer::from(tree_second)
+        );
+    }
+
+    #[test]
+    fn room_size_has_to_be_positive() {
+        let mut params = Day14Params::preset(Preset::Example);
+        assert_eq!(
+            params.set("width", "0").unwrap_err().to_string(),
+            "parameter 'width' has to be positive, got '0'"
+        );
+        assert!(params.set("height", "-7").is_err());
+        params.set("height", "9").unwrap();
+        assert_eq!((params.width, params.height), (11, 9));
+    }
 }
@@ -1,339 +1,344 @@
-use anyhow::{Context, Error, Ok, Result};
-use std::{
-    collections::{HashSet, VecDeque},
-    fs::read_to_string,
+use anyhow::{anyhow, bail, Context, Ok, Result};
+use itertools::Itertools;
+use std::collections::{HashSet, VecDeque};
+
+use crate::{
+    char_cell,
+    direction::Dir4,
+    grid::{Grid, Pos},
+    parse::{self, ParseResult},
+    puzzle::{Answer, Solution},
 };
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum TableItem {
-    WALL,
-    ROBOT,
-    BOX,
-    BoxLeft,
-    BoxRight,
-    EMPTY,
-}
-
-#[derive(Debug, PartialEq, Eq)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
+char_cell! {
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    enum TableItem: "table item" {
+        WALL = '#',
+        ROBOT = '@',
+        BOX = 'O',
+        /// halves of a box on the wide table
+        BoxLeft => '[',
+        BoxRight => ']',
+        EMPTY = '.',
+    }
 }
 
 #[derive(Debug)]
-struct Document {
-    table: Vec<Vec<TableItem>>,
-    moves: Vec<Direction>,
+pub struct Document {
+    table: Grid<TableItem>,
+    moves: Vec<Dir4>,
 }
 
-fn read_document(file_name: &str) -> Result<Document> {
-    let input =
-        read_to_string(format!("./src/day_15/{}.txt", file_name)).context("could not read file")?;
-    let input_parts: Vec<&str> = input.split("\n\n").collect();
+fn read_document(input: &str) -> Result<Document> {
+    let [table_part, moves_part] = parse::sections_n(input)?;
 
-    let table_raw: Vec<Vec<char>> = input_parts
-        .get(0)
-        .context("could not get table part of input")?
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect();
-
-    let table = table_raw
-        .into_iter()
-        .map(|row| {
-            row.into_iter()
-                .map(|cell| match cell {
-                    '#' => Ok(TableItem::WALL),
-                    '@' => Ok(TableItem::ROBOT),
-                    '.' => Ok(TableItem::EMPTY),
-                    'O' => Ok(TableItem::BOX),
-                    _ => Err(Error::msg(format!("unknown table item '{}'", cell))),
-                })
-                .collect()
-        })
-        .collect::<Result<Vec<Vec<TableItem>>>>()?;
+    let table: Grid<TableItem> = table_part.cells()?;
+    let robots = table
+        .iter()
+        .filter(|(_, cell)| **cell == TableItem::ROBOT)
+        .count();
+    if robots != 1 {
+        bail!("expected one robot, found {}", robots);
+    }
 
-    let moves_raw: Vec<char> = input_parts
-        .get(1)
-        .context("could not get moves part of input")?
+    let moves = moves_part
         .lines()
-        .flat_map(|line| line.chars().collect::<Vec<_>>())
-        .collect();
-
-    let moves = moves_raw
-        .into_iter()
-        .map(|direction| match direction {
-            '^' => Ok(Direction::UP),
-            '>' => Ok(Direction::RIGHT),
-            'v' => Ok(Direction::DOWN),
-            '<' => Ok(Direction::LEFT),
-            _ => Err(Error::msg(format!("unknown direction '{}'", direction))),
+        .flat_map(|line| {
+            line.text.chars().enumerate().map(move |(i, direction)| {
+                Dir4::from_arrow(direction)
+                    .ok_or_else(|| line.error(i + 1, format!("unknown direction '{}'", direction)))
+            })
         })
-        .collect::<Result<Vec<Direction>>>()?;
+        .collect::<ParseResult<Vec<Dir4>>>()?;
 
     Ok(Document { table, moves })
 }
 
-fn get_robot_position(table: &Vec<Vec<TableItem>>) -> (usize, usize) {
-    for i in 0..table.len() {
-        for j in 0..table[i].len() {
-            if table[i][j] == TableItem::ROBOT {
-                return (i, j);
-            }
-        }
-    }
-    (0, 0)
+fn get_robot_position(table: &Grid<TableItem>) -> Result<Pos> {
+    table
+        .position_of(&TableItem::ROBOT)
+        .context("table has no robot")
 }
 
-fn part_one() -> Result<()> {
-    fn move_in_direction(direction: &Direction, table: &mut Vec<Vec<TableItem>>) {
-        let (dx, dy) = match *direction {
-            Direction::UP => (-1, 0),
-            Direction::DOWN => (1, 0),
-            Direction::LEFT => (0, -1),
-            Direction::RIGHT => (0, 1),
-        };
-        let (i, j) = get_robot_position(&table);
-        let (nx, ny) = (i as i32 + dx, j as i32 + dy);
-
-        let n = table.len() as i32;
-        let m = table[0].len() as i32;
-
-        if nx < 0 || nx >= n || ny < 0 || ny >= m {
-            return;
-        }
-
-        let (ni, nj) = (nx as usize, ny as usize);
+fn gps_sum(table: &Grid<TableItem>, item: &TableItem) -> i32 {
+    table
+        .iter()
+        .filter(|(_, cell)| *cell == item)
+        .map(|((i, j), _)| i as i32 * 100 + j as i32)
+        .sum()
+}
 
-        match table[ni][nj] {
-            TableItem::EMPTY => {
-                table[i][j] = TableItem::EMPTY;
-                table[ni][nj] = TableItem::ROBOT;
-            }
-            TableItem::BOX => {
-                let mut ci = ni;
-                let mut cj = nj;
-                while table[ci][cj] != TableItem::WALL && table[ci][cj] != TableItem::EMPTY {
-                    ci = (ci as i32 + dx) as usize;
-                    cj = (cj as i32 + dy) as usize;
-                }
-                if table[ci][cj] == TableItem::EMPTY {
-                    table[i][j] = TableItem::EMPTY;
-                    table[ni][nj] = TableItem::ROBOT;
-                    table[ci][cj] = TableItem::BOX;
-                }
-            }
-            TableItem::ROBOT => panic!("found multiple robots!"),
-            _ => (),
-        }
-    }
-    let doc = read_document("input")?;
+pub struct Day15;
 
-    let mut table = doc.table;
-    let moves = doc.moves;
+impl Solution for Day15 {
+    type Input = Document;
+    type Params = ();
 
-    for direction in moves {
-        move_in_direction(&direction, &mut table);
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_document(input)
     }
 
-    let mut result: i32 = 0;
-
-    for i in 0..table.len() {
-        for j in 0..table[i].len() {
-            if table[i][j] != TableItem::BOX {
-                continue;
+    fn part_one(doc: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        fn move_in_direction(direction: Dir4, table: &mut Grid<TableItem>) -> Result<()> {
+            let delta = direction.offset();
+            let robot = get_robot_position(table)?;
+            let Some(next) = table.offset(robot, delta) else {
+                return Ok(());
+            };
+
+            match table[next] {
+                TableItem::EMPTY => {
+                    table[robot] = TableItem::EMPTY;
+                    table[next] = TableItem::ROBOT;
+                }
+                TableItem::BOX => {
+                    let end = table.ray(next, delta).find(|&pos| {
+                        table[pos] == TableItem::WALL || table[pos] == TableItem::EMPTY
+                    });
+                    if let Some(end) = end.filter(|&end| table[end] == TableItem::EMPTY) {
+                        table[robot] = TableItem::EMPTY;
+                        table[next] = TableItem::ROBOT;
+                        table[end] = TableItem::BOX;
+                    }
+                }
+                TableItem::ROBOT => bail!("found multiple robots"),
+                _ => (),
             }
-            result += i as i32 * 100 + j as i32;
+            Ok(())
         }
-    }
-
-    println!("part one result {}", result);
+        let mut table = doc.table.clone();
 
-    Ok(())
-}
-
-fn show_table(table: &Vec<Vec<TableItem>>) {
-    for row in table {
-        for cell in row {
-            print!(
-                "{}",
-                match *cell {
-                    TableItem::WALL => "#",
-                    TableItem::ROBOT => "@",
-                    TableItem::BOX => "O",
-                    TableItem::BoxLeft => "[",
-                    TableItem::BoxRight => "]",
-                    TableItem::EMPTY => ".",
-                }
-            );
+        for &direction in &doc.moves {
+            move_in_direction(direction, &mut table)?;
         }
-        println!("");
-    }
-}
 
-fn part_two() -> Result<()> {
-    fn get_wide_table(table: &Vec<Vec<TableItem>>) -> Vec<Vec<TableItem>> {
-        table
-            .into_iter()
-            .map(|row| {
-                row.into_iter()
-                    .flat_map(|cell| match *cell {
-                        TableItem::WALL => vec![TableItem::WALL, TableItem::WALL],
-                        TableItem::ROBOT => vec![TableItem::ROBOT, TableItem::EMPTY],
-                        TableItem::BOX => vec![TableItem::BoxLeft, TableItem::BoxRight],
-                        TableItem::EMPTY => vec![TableItem::EMPTY, TableItem::EMPTY],
-                        _ => vec![],
-                    })
-                    .collect()
-            })
-            .collect()
+        Ok(gps_sum(&table, &TableItem::BOX).into())
     }
 
-    fn move_in_direction(direction: &Direction, table: &mut Vec<Vec<TableItem>>) {
-        let (dx, dy) = match *direction {
-            Direction::UP => (-1, 0),
-            Direction::DOWN => (1, 0),
-            Direction::LEFT => (0, -1),
-            Direction::RIGHT => (0, 1),
-        };
-        let (i, j) = get_robot_position(&table);
-        let (nx, ny) = (i as i32 + dx, j as i32 + dy);
-
-        let n = table.len() as i32;
-        let m = table[0].len() as i32;
-
-        if nx < 0 || nx >= n || ny < 0 || ny >= m {
-            return;
+    fn part_two(doc: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        fn get_wide_table(table: &Grid<TableItem>) -> Result<Grid<TableItem>> {
+            let rows = table
+                .rows()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| match cell {
+                            TableItem::WALL => Ok([TableItem::WALL, TableItem::WALL]),
+                            TableItem::ROBOT => Ok([TableItem::ROBOT, TableItem::EMPTY]),
+                            TableItem::BOX => Ok([TableItem::BoxLeft, TableItem::BoxRight]),
+                            TableItem::EMPTY => Ok([TableItem::EMPTY, TableItem::EMPTY]),
+                            cell => Err(anyhow!("'{}' is already part of a wide table", cell)),
+                        })
+                        .flatten_ok()
+                        .collect::<Result<Vec<_>>>()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Grid::from_rows(rows)
         }
 
-        let (ni, nj) = (nx as usize, ny as usize);
+        fn move_in_direction(direction: Dir4, table: &mut Grid<TableItem>) -> Result<()> {
+            let delta = direction.offset();
+            let (i, j) = get_robot_position(table)?;
+            let Some((ni, nj)) = table.offset((i, j), delta) else {
+                return Ok(());
+            };
+
+            match table[(ni, nj)] {
+                TableItem::EMPTY => {
+                    table[(i, j)] = TableItem::EMPTY;
+                    table[(ni, nj)] = TableItem::ROBOT;
+                }
+                TableItem::BoxLeft | TableItem::BoxRight => {
+                    let is_horizontal = direction.is_horizontal();
+
+                    if is_horizontal {
+                        let mut positions: Vec<Pos> = vec![];
+                        for pos in table.ray((ni, nj), delta) {
+                            positions.push(pos);
+                            if table[pos] == TableItem::WALL || table[pos] == TableItem::EMPTY {
+                                break;
+                            }
+                        }
+                        if positions
+                            .last()
+                            .is_none_or(|&end| table[end] == TableItem::WALL)
+                        {
+                            return Ok(());
+                        }
 
-        match table[ni][nj] {
-            TableItem::EMPTY => {
-                table[i][j] = TableItem::EMPTY;
-                table[ni][nj] = TableItem::ROBOT;
-            }
-            TableItem::BoxLeft | TableItem::BoxRight => {
-                let is_horizontal = vec![Direction::LEFT, Direction::RIGHT].contains(direction);
-
-                if is_horizontal {
-                    let mut cj = nj;
-                    let mut positions: Vec<usize> = vec![cj];
-                    while table[i][cj] != TableItem::WALL && table[i][cj] != TableItem::EMPTY {
-                        cj = (cj as i32 + dy) as usize;
-                        positions.push(cj);
+                        positions.windows(2).rev().for_each(|window| {
+                            table[window[1]] = table[window[0]].clone();
+                        });
+                        table[(i, j)] = TableItem::EMPTY;
+                        table[(ni, nj)] = TableItem::ROBOT;
+                        return Ok(());
                     }
-                    if table[i][cj] == TableItem::WALL {
-                        return;
+                    // vertical
+                    let mut queue: VecDeque<Pos> = VecDeque::new();
+                    let mut was: HashSet<Pos> = HashSet::new();
+                    queue.push_back((ni, j));
+
+                    if table[(ni, j)] == TableItem::BoxLeft {
+                        queue.push_back((ni, j + 1));
+                    } else if table[(ni, j)] == TableItem::BoxRight {
+                        queue.push_back((ni, j - 1));
                     }
 
-                    positions.windows(2).rev().for_each(|window| {
-                        table[i][window[1]] = table[i][window[0]].clone();
-                    });
-                    table[i][j] = TableItem::EMPTY;
-                    table[ni][nj] = TableItem::ROBOT;
-                    return;
-                }
-                // vertical
-                let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
-                let mut was: HashSet<(usize, usize)> = HashSet::new();
-                queue.push_back((ni, j));
-
-                if table[ni][j] == TableItem::BoxLeft {
-                    queue.push_back((ni, j + 1));
-                } else if table[ni][j] == TableItem::BoxRight {
-                    queue.push_back((ni, j - 1));
-                }
-
-                while let Some(top) = queue.pop_front() {
-                    was.insert(top);
-                    let (top_i, top_j) = top;
-                    let ci = (top_i as i32 + dx) as usize;
-
-                    match table[ci][top_j] {
-                        TableItem::WALL | TableItem::EMPTY => {}
-                        TableItem::BoxLeft => {
-                            if !was.contains(&(ci, top_j + 1)) {
-                                was.insert((ci, top_j + 1));
-                                queue.push_back((ci, top_j + 1));
-                            }
-                            if !was.contains(&(ci, top_j)) {
-                                was.insert((ci, top_j));
-                                queue.push_back((ci, top_j));
-                            }
-                        }
-                        TableItem::BoxRight => {
-                            if !was.contains(&(ci, top_j - 1)) {
-                                was.insert((ci, top_j - 1));
-                                queue.push_back((ci, top_j - 1));
+                    while let Some(top) = queue.pop_front() {
+                        was.insert(top);
+                        let Some((ci, top_j)) = table.offset(top, delta) else {
+                            continue;
+                        };
+
+                        match table[(ci, top_j)] {
+                            TableItem::WALL | TableItem::EMPTY => {}
+                            TableItem::BoxLeft => {
+                                if !was.contains(&(ci, top_j + 1)) {
+                                    was.insert((ci, top_j + 1));
+                                    queue.push_back((ci, top_j + 1));
+                                }
+                                if !was.contains(&(ci, top_j)) {
+                                    was.insert((ci, top_j));
+                                    queue.push_back((ci, top_j));
+                                }
                             }
-                            if !was.contains(&(ci, top_j)) {
-                                was.insert((ci, top_j));
-                                queue.push_back((ci, top_j));
+                            TableItem::BoxRight => {
+                                if !was.contains(&(ci, top_j - 1)) {
+                                    was.insert((ci, top_j - 1));
+                                    queue.push_back((ci, top_j - 1));
+                                }
+                                if !was.contains(&(ci, top_j)) {
+                                    was.insert((ci, top_j));
+                                    queue.push_back((ci, top_j));
+                                }
                             }
+                            _ => {}
                         }
-                        _ => {}
                     }
-                }
 
-                let can_move = was.iter().all(|&(ci, cj)| {
-                    let nci = ci as i32 + dx;
-                    nci >= 0 && nci < n && table[nci as usize][cj] != TableItem::WALL
-                });
-
-                if can_move {
-                    let table_before = table.clone();
-                    was.iter()
-                        .for_each(|&(ci, cj)| table[ci][cj] = TableItem::EMPTY);
-                    was.iter().for_each(|&(ci, cj)| {
-                        let nci = ci as i32 + dx;
-                        table[nci as usize][cj] = table_before[ci][cj].clone();
+                    let can_move = was.iter().all(|&pos| {
+                        table
+                            .offset(pos, delta)
+                            .is_some_and(|next| table[next] != TableItem::WALL)
                     });
-                    table[i][j] = TableItem::EMPTY;
-                    table[ni][nj] = TableItem::ROBOT;
+
+                    if can_move {
+                        let table_before = table.clone();
+                        was.iter().for_each(|&pos| table[pos] = TableItem::EMPTY);
+                        was.iter().for_each(|&pos| {
+                            let next = table.offset(pos, delta).expect("checked by can_move");
+                            table[next] = table_before[pos].clone();
+                        });
+                        table[(i, j)] = TableItem::EMPTY;
+                        table[(ni, nj)] = TableItem::ROBOT;
+                    }
                 }
+                TableItem::ROBOT => bail!("found multiple robots"),
+                _ => (),
             }
-            _ => (),
+            Ok(())
         }
-    }
 
-    let doc = read_document("input")?;
+        let mut table = get_wide_table(&doc.table)?;
+        let moves = &doc.moves;
 
-    let mut table = get_wide_table(&doc.table);
-    let moves = doc.moves;
+        for i in 0..moves.len() {
+            move_in_direction(moves[i], &mut table)?;
+        }
 
-    // println!("Initial:");
-    // show_table(&table);
-    // let mut line: String = Default::default();
+        let result = gps_sum(&table, &TableItem::BoxLeft);
 
-    for i in 0..moves.len() {
-        // let _ = std::io::stdin().read_line(&mut line);
-        move_in_direction(&moves[i], &mut table);
-        // println!("Move {}: {:?}", i, moves[i]);
-        // show_table(&table);
+        Ok(result.into())
     }
+}
 
-    let mut result: i32 = 0;
-
-    for i in 0..table.len() {
-        for j in 0..table[i].len() {
-            if table[i][j] != TableItem::BoxLeft {
-                continue;
-            }
-            result += i as i32 * 100 + j as i32;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+";
+
+    const SMALL_EXAMPLE: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part_one(&input, &()).unwrap(), Answer::from(10092));
     }
 
-    // println!("End:");
-    // show_table(&table);
+    #[test]
+    fn part_two_example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part_two(&input, &()).unwrap(), Answer::from(9021));
+    }
 
-    println!("part two result {}", result);
+    #[test]
+    fn part_one_small_example() {
+        let input = Day15::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(Day15::part_one(&input, &()).unwrap(), Answer::from(2028));
+    }
 
-    Ok(())
-}
+    #[test]
+    fn read_document_example() {
+        let document = read_document(SMALL_EXAMPLE).unwrap();
+        assert_eq!(document.table.height(), 8);
+        assert_eq!(document.table.width(), 8);
+        assert_eq!(document.table[(2, 2)], TableItem::ROBOT);
+        assert_eq!(document.table[(1, 3)], TableItem::BOX);
+        assert_eq!(document.moves.len(), 15);
+        assert_eq!(document.moves[0], Dir4::West);
+        assert_eq!(document.moves[1], Dir4::North);
+    }
 
-pub fn solve() -> Result<()> {
-    part_two()
+    #[test]
+    fn read_document_rejects_unknown_items() {
+        assert!(read_document("#x#\n\n<").is_err());
+        assert!(read_document("#.#\n\n<?").is_err());
+        assert!(read_document("#.#").is_err());
+        assert_eq!(
+            read_document("#.#\n\n<").unwrap_err().to_string(),
+            "expected one robot, found 0"
+        );
+        assert_eq!(
+            read_document("#@@#\n\n<").unwrap_err().to_string(),
+            "expected one robot, found 2"
+        );
+        // box halves only appear on the wide table built in part two
+        assert_eq!(
+            read_document("#[]@#\n\n<").unwrap_err().to_string(),
+            "line 1, column 2: unknown table item '['"
+        );
+    }
 }
@@ -1,203 +1,153 @@
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    fs::read_to_string,
-    i32,
-};
+use std::collections::HashSet;
 
-use anyhow::{Context, Error, Result};
+use anyhow::{Context, Result};
 
-#[derive(Debug, PartialEq, Eq)]
-enum MazeCell {
-    Wall,
-    Empty,
-    EndPoint,
-    StartPoint,
-    Path,
-}
+use crate::{
+    char_cell,
+    direction::Dir4,
+    grid::{Grid, Pos},
+    puzzle::{Answer, Solution},
+    search::{dijkstra, Search},
+};
 
-fn read_maze(file_name: &str) -> Result<Vec<Vec<MazeCell>>> {
-    let input =
-        read_to_string(format!("./src/day_16/{}.txt", file_name)).context("could not read file")?;
-
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|cell| match cell {
-                    '#' => Ok(MazeCell::Wall),
-                    '.' => Ok(MazeCell::Empty),
-                    'E' => Ok(MazeCell::EndPoint),
-                    'S' => Ok(MazeCell::StartPoint),
-                    _ => Err(Error::msg(format!("unknown maze cell '{}'", cell))),
-                })
-                .collect()
-        })
-        .collect()
+char_cell! {
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum MazeCell: "maze cell" {
+        Wall = '#',
+        Empty = '.',
+        EndPoint = 'E',
+        StartPoint = 'S',
+        /// tile on one of the best paths, as drawn in the puzzle
+        Path => 'O',
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
+fn read_maze(input: &str) -> Result<Grid<MazeCell>> {
+    Grid::parse_cells(input)
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct StepState {
-    position: (usize, usize),
-    direction: Direction,
-    cost: i32,
-    positions_set: HashSet<(usize, usize)>,
+/// Reindeer standing on a tile and facing a direction.
+type Reindeer = (Pos, Dir4);
+
+fn find_paths(maze: &Grid<MazeCell>) -> Result<Search<Reindeer>> {
+    let start_point = maze
+        .position_of(&MazeCell::StartPoint)
+        .context("maze has no start point")?;
+    let end_point = maze
+        .position_of(&MazeCell::EndPoint)
+        .context("maze has no end point")?;
+
+    let successors = |&(position, direction): &Reindeer| {
+        let turns = [direction.turn_left(), direction.turn_right()]
+            .map(|turned| ((position, turned), 1000));
+        let forward = maze
+            .step(position, direction)
+            .filter(|&next| maze[next] != MazeCell::Wall)
+            .map(|next| ((next, direction), 1));
+        turns.into_iter().chain(forward)
+    };
+
+    Ok(dijkstra(
+        (start_point, Dir4::East),
+        successors,
+        |&(position, _)| position == end_point,
+    ))
 }
 
-impl Ord for StepState {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
+/// Returns the lowest score to reach the end (if reachable) and the number of
+/// tiles that are part of at least one of the best paths.
+fn find_best_paths(maze: &Grid<MazeCell>) -> Result<(Option<u64>, usize)> {
+    let search = find_paths(maze)?;
 
-impl PartialOrd for StepState {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
+    let tiles: HashSet<Pos> = search
+        .on_best_paths(search.goals())
+        .into_iter()
+        .map(|(position, _)| position)
+        .collect();
 
-pub fn solve() -> Result<()> {
-    let maze = read_maze("input")?;
+    Ok((search.cost(), tiles.len()))
+}
 
-    let n = maze.len();
-    let m = maze[0].len();
+pub struct Day16;
 
-    let mut start_point: (usize, usize) = Default::default();
-    let mut end_point: (usize, usize) = Default::default();
+impl Solution for Day16 {
+    type Input = Grid<MazeCell>;
+    type Params = ();
 
-    for i in 0..n {
-        for j in 0..m {
-            match maze[i][j] {
-                MazeCell::StartPoint => start_point = (i, j),
-                MazeCell::EndPoint => end_point = (i, j),
-                _ => {}
-            };
-        }
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_maze(input)
     }
 
-    let start_direction = Direction::East;
-
-    let mut dist: HashMap<((usize, usize), Direction), i32> = HashMap::new();
-    let mut heap: BinaryHeap<StepState> = BinaryHeap::new();
-
-    let mut all_positions: HashSet<(usize, usize)> = HashSet::new();
-
-    dist.insert((start_point, start_direction), 0);
-    heap.push(StepState {
-        position: start_point,
-        direction: start_direction,
-        cost: 0,
-        positions_set: HashSet::from_iter(vec![start_point].into_iter()),
-    });
-
-    while let Some(StepState {
-        position,
-        direction,
-        cost,
-        positions_set,
-    }) = heap.pop()
-    {
-        if position == end_point {
-            let all_directions = vec![
-                Direction::North,
-                Direction::South,
-                Direction::West,
-                Direction::East,
-            ];
-
-            let min = all_directions
-                .into_iter()
-                .map(|direction| dist.get(&(end_point, direction)).unwrap_or(&i32::MAX))
-                .min();
-
-            if cost == *min.unwrap_or(&i32::MAX) {
-                positions_set.into_iter().for_each(|item| {
-                    all_positions.insert(item);
-                });
-            }
-            continue;
-        }
-
-        if cost > *dist.get(&(position, direction)).unwrap_or(&i32::MAX) {
-            continue;
-        }
-
-        // rotate
-        let rotate_directions: Vec<Direction> = match direction {
-            Direction::North | Direction::South => vec![Direction::East, Direction::West],
-            Direction::East | Direction::West => vec![Direction::North, Direction::South],
-        };
+    fn part_one(maze: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let (lowest_score, _) = find_best_paths(maze)?;
+        let result = lowest_score.context("no path found to the end point")?;
 
-        rotate_directions.into_iter().for_each(|rotate_direction| {
-            if cost + 1000 <= *dist.get(&(position, rotate_direction)).unwrap_or(&i32::MAX) {
-                heap.push(StepState {
-                    position,
-                    direction: rotate_direction,
-                    cost: cost + 1000,
-                    positions_set: positions_set.clone(),
-                });
-                dist.insert((position, rotate_direction), cost + 1000);
-            }
-        });
-
-        let (i, j) = position;
-        let (ni, nj): (usize, usize) = match direction {
-            Direction::North => (i - 1, j),
-            Direction::South => (i + 1, j),
-            Direction::East => (i, j + 1),
-            Direction::West => (i, j - 1),
-        };
-
-        if ni >= n || nj >= m || maze[ni][nj] == MazeCell::Wall {
-            continue;
-        }
-
-        let mut next_positions_set = positions_set.clone();
-        next_positions_set.insert((ni, nj));
+        Ok(result.into())
+    }
 
-        let next_state = StepState {
-            position: (ni, nj),
-            direction,
-            cost: cost + 1,
-            positions_set: next_positions_set,
-        };
+    fn part_two(maze: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let (lowest_score, tiles_count) = find_best_paths(maze)?;
+        lowest_score.context("no path found to the end point")?;
 
-        if next_state.cost
-            <= *dist
-                .get(&(next_state.position, direction))
-                .unwrap_or(&i32::MAX)
-        {
-            heap.push(next_state.clone());
-            dist.insert((next_state.position, direction), cost + 1);
-        }
+        Ok(tiles_count.into())
     }
+}
 
-    let all_directions = vec![
-        Direction::North,
-        Direction::South,
-        Direction::West,
-        Direction::East,
-    ];
-
-    let min = all_directions
-        .into_iter()
-        .map(|direction| dist.get(&(end_point, direction)).unwrap_or(&i32::MAX))
-        .min();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part_one(&input, &()).unwrap(), Answer::from(7036));
+    }
 
-    if let Some(&result) = min {
-        println!("part one result {}", result);
-    } else {
-        println!("no path found to {:?}", end_point);
+    #[test]
+    fn part_two_example() {
+        let input = Day16::parse(EXAMPLE).unwrap();
+        assert_eq!(Day16::part_two(&input, &()).unwrap(), Answer::from(45));
     }
 
-    println!("part two result {}", all_positions.len());
+    #[test]
+    fn mazes_need_a_start_and_an_end() {
+        let error = |input: &str| {
+            Day16::part_two(&Day16::parse(input).unwrap(), &())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(error(""), "maze has no start point");
+        assert_eq!(error("#S.#"), "maze has no end point");
+    }
 
-    Ok(())
+    #[test]
+    fn unreachable_end_is_an_error() {
+        let input = Day16::parse("#####\n#S#E#\n#####").unwrap();
+        assert_eq!(
+            Day16::part_one(&input, &()).unwrap_err().to_string(),
+            "no path found to the end point"
+        );
+        assert_eq!(
+            Day16::part_two(&input, &()).unwrap_err().to_string(),
+            "no path found to the end point"
+        );
+    }
 }
@@ -0,0 +1,270 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use super::disassembler::{Opcode, Register};
+use crate::parse::{self, Line, ParseResult};
+
+/// Registers and program of an assembled source, displayed in the puzzle input format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assembly {
+    pub registers: [i64; 3],
+    pub program: Vec<i64>,
+}
+
+impl Display for Assembly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (register, value) in Register::ALL.iter().zip(&self.registers) {
+            writeln!(f, "Register {}: {}", register, value)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Program: {}",
+            self.program
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+enum Statement<'a> {
+    Instruction {
+        opcode: Opcode,
+        operand: Option<&'a str>,
+    },
+    Data(Vec<&'a str>),
+}
+
+/// Turns mnemonic source into a program, the opposite of [`super::disassembler::listing`].
+///
+/// Every line holds an optional `label:`, then an instruction such as `adv 3`, `out A` or
+/// `jnz loop`, or one of the directives `.data 1, 2` and `.register A 729`. Anything after `;`
+/// is a comment. Numeric operands are the raw operand, so `adv 4` is the same as `adv A`.
+pub fn assemble(source: &str) -> ParseResult<Assembly> {
+    let mut registers = [0; 3];
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut statements: Vec<(Line, Statement)> = vec![];
+    let mut address = 0;
+
+    for line in parse::lines(source) {
+        let code = line.text.split(';').next().unwrap_or_default();
+        let code = match code.split_once(':') {
+            Some((name, rest)) => {
+                let name = name.trim();
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(line.error(line.column_of(name), "invalid label name"));
+                }
+                if labels.insert(name, address).is_some() {
+                    return Err(line.error(
+                        line.column_of(name),
+                        format!("label '{}' is defined twice", name),
+                    ));
+                }
+                rest
+            }
+            None => code,
+        };
+
+        let mut words = code.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        let rest =
+            code[code.find(keyword).expect("keyword is part of the code") + keyword.len()..].trim();
+
+        let statement = match keyword {
+            ".register" => {
+                let (name, value) = match rest.split_whitespace().collect::<Vec<_>>()[..] {
+                    [name, value] => (name, value),
+                    _ => {
+                        return Err(line.error(
+                            line.column_of(keyword),
+                            "expected '.register <A|B|C> <value>'",
+                        ))
+                    }
+                };
+                let register = Register::from_name(name).ok_or_else(|| {
+                    line.error(line.column_of(name), format!("unknown register '{}'", name))
+                })?;
+                registers[register.index()] = line.parse(value)?;
+                continue;
+            }
+            ".data" => Statement::Data(rest.split(',').collect()),
+            mnemonic => {
+                let opcode = Opcode::from_mnemonic(mnemonic).ok_or_else(|| {
+                    line.error(
+                        line.column_of(keyword),
+                        format!("unknown instruction '{}'", mnemonic),
+                    )
+                })?;
+                if words.nth(1).is_some() {
+                    return Err(line.error(
+                        line.column_of(keyword),
+                        format!("'{}' takes a single operand", mnemonic),
+                    ));
+                }
+                Statement::Instruction {
+                    opcode,
+                    operand: (!rest.is_empty()).then_some(rest),
+                }
+            }
+        };
+
+        address += match &statement {
+            Statement::Instruction { .. } => 2,
+            Statement::Data(values) => values.len(),
+        };
+        statements.push((line, statement));
+    }
+
+    let mut program = vec![];
+    for (line, statement) in statements {
+        match statement {
+            Statement::Instruction { opcode, operand } => {
+                program.push(opcode.code());
+                program.push(encode_operand(&line, opcode, operand, &labels)?);
+            }
+            Statement::Data(values) => {
+                for value in values {
+                    program.push(line.parse(value)?);
+                }
+            }
+        }
+    }
+
+    Ok(Assembly { registers, program })
+}
+
+fn encode_operand(
+    line: &Line,
+    opcode: Opcode,
+    operand: Option<&str>,
+    labels: &HashMap<&str, usize>,
+) -> ParseResult<i64> {
+    let Some(operand) = operand else {
+        // bxc ignores its operand
+        return match opcode {
+            Opcode::Bxc => Ok(0),
+            _ => Err(line.error(
+                line.text.len() + 1,
+                format!("'{}' needs an operand", opcode.mnemonic()),
+            )),
+        };
+    };
+    let column = line.column_of(operand);
+
+    let value = match (opcode, Register::from_name(operand), labels.get(operand)) {
+        (_, Some(register), _) if opcode.takes_combo() => 4 + register.index() as i64,
+        (Opcode::Jnz, _, Some(&target)) => i64::try_from(target).unwrap_or(i64::MAX),
+        _ if operand.chars().all(|c| c.is_ascii_digit()) => line.parse(operand)?,
+        (Opcode::Jnz, _, None) => {
+            return Err(line.error(column, format!("unknown label '{}'", operand)))
+        }
+        _ => {
+            return Err(line.error(
+                column,
+                format!("invalid operand '{}' for '{}'", operand, opcode.mnemonic()),
+            ))
+        }
+    };
+
+    if !(0..8).contains(&value) {
+        return Err(line.error(
+            column,
+            format!(
+                "operand '{}' does not fit in three bits ({})",
+                operand, value
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_17::{disassembler::listing, solution::Day17};
+    use crate::puzzle::Solution;
+
+    #[test]
+    fn assembles_labels_and_comments() {
+        let assembly = assemble(
+            "\
+; the first example of the puzzle
+.register A 729
+loop: adv 1   ; drop a bit
+    out A
+    jnz loop
+",
+        )
+        .unwrap();
+        assert_eq!(assembly.program, vec![0, 1, 5, 4, 3, 0]);
+        assert_eq!(
+            assembly.to_string(),
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n"
+        );
+
+        let computer = Day17::parse(&assembly.to_string()).unwrap();
+        assert_eq!(computer.program(), assembly.program.as_slice());
+        assert_eq!(computer.registers(), &assembly.registers);
+    }
+
+    #[test]
+    fn round_trips_the_input_file() {
+        let input = "Register A: 2024\nRegister B: 7\nRegister C: -1\n\nProgram: 0,3,5,4,3,0\n";
+        let computer = Day17::parse(input).unwrap();
+        assert_eq!(
+            assemble(&computer.disassemble()).unwrap().to_string(),
+            input
+        );
+    }
+
+    #[test]
+    fn round_trips_the_disassembler() {
+        let every_pair: Vec<i64> = (0..64).flat_map(|i| [i / 8, i % 8]).collect();
+        let programs = [
+            every_pair,
+            vec![2, 4, 1, 5, 7, 5, 4, 3, 6, 7, 3, 3, 5],
+            vec![3, 0, 9, -1, 5],
+            vec![],
+        ];
+        for program in programs {
+            assert_eq!(assemble(&listing(&program)).unwrap().program, program);
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_source() {
+        let error = |source: &str| assemble(source).unwrap_err().to_string();
+
+        assert_eq!(
+            error("adv 1\nmul 2"),
+            "line 2, column 1: unknown instruction 'mul'"
+        );
+        assert_eq!(
+            error("adv 1\njnz end"),
+            "line 2, column 5: unknown label 'end'"
+        );
+        assert_eq!(
+            error("out 8"),
+            "line 1, column 5: operand '8' does not fit in three bits (8)"
+        );
+        assert_eq!(
+            error("bxl A"),
+            "line 1, column 5: invalid operand 'A' for 'bxl'"
+        );
+        assert_eq!(
+            error("adv 1\nadv 1\nadv 1\nadv 1\nend: out A\njnz end"),
+            "line 6, column 5: operand 'end' does not fit in three bits (8)"
+        );
+        assert_eq!(
+            error("a: adv 1\na: out A"),
+            "line 2, column 1: label 'a' is defined twice"
+        );
+        assert_eq!(error("out"), "line 1, column 4: 'out' needs an operand");
+    }
+}
@@ -0,0 +1,295 @@
+use super::{
+    disassembler::{Opcode, Register},
+    solution::{Computer, Halt},
+};
+
+/// One executed instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub pointer: usize,
+    pub opcode: Opcode,
+    pub operand: i64,
+    pub registers_before: [i64; 3],
+    pub registers_after: [i64; 3],
+    /// value `out` emitted, if the instruction was one
+    pub output: Option<i64>,
+}
+
+/// Every step of a run, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    /// why the run ended, `None` while it is still going
+    pub halt: Option<Halt>,
+}
+
+impl Trace {
+    /// One row per step with a header, registers before and after as separate columns and an
+    /// empty output column for instructions that print nothing.
+    pub fn to_csv(&self) -> String {
+        let registers = |suffix: &str| {
+            Register::ALL
+                .iter()
+                .map(|register| format!("{}_{}", register.name().to_ascii_lowercase(), suffix))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let mut csv = format!(
+            "step,pointer,opcode,operand,{},{},output\n",
+            registers("before"),
+            registers("after")
+        );
+
+        for (i, step) in self.steps.iter().enumerate() {
+            let values = |registers: &[i64]| {
+                registers
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                i,
+                step.pointer,
+                step.opcode.mnemonic(),
+                step.operand,
+                values(&step.registers_before),
+                values(&step.registers_after),
+                step.output
+                    .map(|value| value.to_string())
+                    .unwrap_or_default()
+            ));
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+}
+
+impl Comparison {
+    pub fn holds(self, left: i64, right: i64) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::Less => left < right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+/// Condition checked before an instruction runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    Pointer(usize),
+    Register(Register, Comparison, i64),
+}
+
+impl Breakpoint {
+    pub fn hits(&self, computer: &Computer) -> bool {
+        match *self {
+            Breakpoint::Pointer(pointer) => computer.pointer() == pointer,
+            Breakpoint::Register(register, comparison, value) => {
+                comparison.holds(computer.registers()[register.index()], value)
+            }
+        }
+    }
+}
+
+/// Why the debugger handed control back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// index into the breakpoints, the instruction at the pointer has not run yet
+    Breakpoint(usize),
+    Output(i64),
+    Halted(Halt),
+}
+
+/// Runs a [`Computer`] step by step, keeping a [`Trace`] of everything it executed.
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    computer: Computer,
+    breakpoints: Vec<Breakpoint>,
+    trace: Trace,
+    /// the last run stopped at a breakpoint and no instruction has run since
+    at_breakpoint: bool,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Self {
+        Debugger {
+            computer,
+            breakpoints: vec![],
+            trace: Trace::default(),
+            at_breakpoint: false,
+        }
+    }
+
+    /// Returns the index that [`Stop::Breakpoint`] reports for it.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    pub fn into_trace(self) -> Trace {
+        self.trace
+    }
+
+    /// Runs a single instruction, or tells why the program has halted.
+    pub fn step(&mut self) -> Result<&Step, Halt> {
+        self.at_breakpoint = false;
+        match self.computer.step() {
+            Ok(step) => {
+                self.trace.steps.push(step);
+                Ok(self.trace.steps.last().expect("just pushed"))
+            }
+            Err(halt) => {
+                self.trace.halt = Some(halt);
+                Err(halt)
+            }
+        }
+    }
+
+    /// Runs until a breakpoint hits or the program halts. Breakpoints are checked before the
+    /// first instruction too, except when resuming from a breakpoint, so calling this again
+    /// after one moves on.
+    pub fn run(&mut self) -> Stop {
+        self.run_until(|_| false)
+    }
+
+    /// Like [`Debugger::run`], but also stops right after the next `out`.
+    pub fn run_until_output(&mut self) -> Stop {
+        self.run_until(|step| step.output.is_some())
+    }
+
+    fn run_until(&mut self, done: impl Fn(&Step) -> bool) -> Stop {
+        loop {
+            if !self.at_breakpoint {
+                if let Some(index) = self
+                    .breakpoints
+                    .iter()
+                    .position(|breakpoint| breakpoint.hits(&self.computer))
+                {
+                    self.at_breakpoint = true;
+                    return Stop::Breakpoint(index);
+                }
+            }
+
+            match self.step() {
+                Err(halt) => return Stop::Halted(halt),
+                Ok(step) if done(step) => {
+                    return Stop::Output(step.output.expect("only outputs end a run early"))
+                }
+                Ok(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day_17::solution::Day17, puzzle::Solution};
+
+    const EXAMPLE: &str = "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+    #[test]
+    fn traces_every_step() {
+        let mut computer = Day17::parse(EXAMPLE).unwrap();
+        let trace = computer.run_traced();
+
+        assert_eq!(trace.steps.len(), 30);
+        assert_eq!(trace.halt, Some(Halt::RanOffEnd));
+        assert_eq!(
+            trace.steps[1],
+            Step {
+                pointer: 2,
+                opcode: Opcode::Out,
+                operand: 4,
+                registers_before: [364, 0, 0],
+                registers_after: [364, 0, 0],
+                output: Some(4),
+            }
+        );
+        let outputs: Vec<i64> = trace.steps.iter().filter_map(|step| step.output).collect();
+        assert_eq!(outputs, computer.output());
+
+        let csv = trace.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("step,pointer,opcode,operand,a_before,b_before,c_before,a_after,b_after,c_after,output")
+        );
+        assert_eq!(lines.next(), Some("0,0,adv,1,729,0,0,364,0,0,"));
+        assert_eq!(lines.next(), Some("1,2,out,4,364,0,0,364,0,0,4"));
+        assert_eq!(csv.lines().count(), 31);
+    }
+
+    #[test]
+    fn stops_at_breakpoints_and_outputs() {
+        let mut debugger = Debugger::new(Day17::parse(EXAMPLE).unwrap());
+
+        assert_eq!(debugger.run_until_output(), Stop::Output(4));
+        assert_eq!(debugger.computer().pointer(), 4);
+
+        debugger.add_breakpoint(Breakpoint::Register(Register::B, Comparison::NotEqual, 0));
+        let jump = debugger.add_breakpoint(Breakpoint::Pointer(4));
+        // already at the jump, the output stop does not skip the new breakpoint
+        assert_eq!(debugger.run(), Stop::Breakpoint(jump));
+        assert_eq!(debugger.trace().steps.len(), 2);
+        // resuming runs the jump before the breakpoint can hit again
+        assert_eq!(debugger.run(), Stop::Breakpoint(jump));
+        assert_eq!(debugger.trace().steps.len(), 5);
+
+        debugger.clear_breakpoints();
+        let small =
+            debugger.add_breakpoint(Breakpoint::Register(Register::A, Comparison::Less, 50));
+        assert_eq!(debugger.run(), Stop::Breakpoint(small));
+        assert_eq!(debugger.computer().registers()[0], 45);
+
+        assert_eq!(debugger.step().map(|step| step.output), Ok(Some(5)));
+        debugger.clear_breakpoints();
+        assert_eq!(debugger.run(), Stop::Halted(Halt::RanOffEnd));
+        assert_eq!(debugger.step(), Err(Halt::RanOffEnd));
+
+        let trace = debugger.into_trace();
+        assert_eq!((trace.steps.len(), trace.halt), (30, Some(Halt::RanOffEnd)));
+    }
+
+    #[test]
+    fn breakpoints_hit_before_the_first_instruction() {
+        let mut debugger = Debugger::new(Day17::parse(EXAMPLE).unwrap());
+        let start = debugger.add_breakpoint(Breakpoint::Pointer(0));
+
+        assert_eq!(debugger.run(), Stop::Breakpoint(start));
+        assert!(debugger.trace().steps.is_empty());
+
+        assert_eq!(debugger.run(), Stop::Breakpoint(start));
+        assert_eq!(debugger.trace().steps.len(), 3);
+        assert_eq!(debugger.computer().registers()[0], 364);
+    }
+}
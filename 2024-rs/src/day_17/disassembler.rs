@@ -0,0 +1,340 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    pub fn from_code(code: i64) -> Option<Opcode> {
+        usize::try_from(code)
+            .ok()
+            .and_then(|code| Opcode::ALL.get(code).copied())
+    }
+
+    pub fn code(self) -> i64 {
+        self as i64
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Opcode> {
+        Opcode::ALL
+            .into_iter()
+            .find(|opcode| opcode.mnemonic() == mnemonic)
+    }
+
+    /// Whether the operand goes through [`Combo`] decoding.
+    pub fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+impl Register {
+    pub const ALL: [Register; 3] = [Register::A, Register::B, Register::C];
+
+    /// Position in `Computer::registers`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> char {
+        match self {
+            Register::A => 'A',
+            Register::B => 'B',
+            Register::C => 'C',
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Register> {
+        Register::ALL
+            .into_iter()
+            .find(|register| name.len() == 1 && name.starts_with(register.name()))
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Value a combo operand stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Combo {
+    Literal(i64),
+    Register(Register),
+    /// operand 7, which valid programs never use
+    Reserved,
+}
+
+impl Combo {
+    pub fn decode(operand: i64) -> Combo {
+        match operand {
+            0..=3 => Combo::Literal(operand),
+            4 => Combo::Register(Register::A),
+            5 => Combo::Register(Register::B),
+            6 => Combo::Register(Register::C),
+            _ => Combo::Reserved,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combo::Literal(value) => write!(f, "{}", value),
+            Combo::Register(register) => write!(f, "{}", register),
+            Combo::Reserved => write!(f, "7"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: Opcode,
+    /// raw operand, always in `0..8`
+    pub operand: i64,
+}
+
+impl Instruction {
+    pub fn combo(&self) -> Option<Combo> {
+        self.opcode
+            .takes_combo()
+            .then(|| Combo::decode(self.operand))
+    }
+
+    /// Where `jnz` continues when A is not zero.
+    pub fn jump_target(&self) -> Option<usize> {
+        (self.opcode == Opcode::Jnz).then_some(self.operand as usize)
+    }
+
+    pub fn uses_reserved_operand(&self) -> bool {
+        self.combo() == Some(Combo::Reserved)
+    }
+
+    /// What the instruction does, in terms of the registers.
+    pub fn describe(&self) -> String {
+        let combo = self.combo().map(|combo| combo.to_string());
+        let combo = combo.as_deref().unwrap_or_default();
+        match self.opcode {
+            Opcode::Adv => format!("A = A >> {}", combo),
+            Opcode::Bxl => format!("B = B ^ {}", self.operand),
+            Opcode::Bst => format!("B = {} & 7", combo),
+            Opcode::Jnz => format!("if A != 0 jump to {}", self.operand),
+            Opcode::Bxc => "B = B ^ C".to_string(),
+            Opcode::Out => format!("output {} & 7", combo),
+            Opcode::Bdv => format!("B = A >> {}", combo),
+            Opcode::Cdv => format!("C = A >> {}", combo),
+        }
+    }
+}
+
+/// One decoded pair of the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    Instruction(Instruction),
+    /// values that do not form an instruction: a lone trailing opcode, or numbers outside `0..8`
+    Data {
+        address: usize,
+        values: Vec<i64>,
+    },
+}
+
+impl Item {
+    pub fn address(&self) -> usize {
+        match self {
+            Item::Instruction(instruction) => instruction.address,
+            Item::Data { address, .. } => *address,
+        }
+    }
+}
+
+/// Decodes the program pair by pair, the way the instruction pointer walks it without jumps.
+pub fn disassemble(program: &[i64]) -> Vec<Item> {
+    program
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let address = i * 2;
+            match (pair, pair.first().and_then(|&code| Opcode::from_code(code))) {
+                (&[_, operand], Some(opcode)) if (0..8).contains(&operand) => {
+                    Item::Instruction(Instruction {
+                        address,
+                        opcode,
+                        operand,
+                    })
+                }
+                _ => Item::Data {
+                    address,
+                    values: pair.to_vec(),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Name of the label placed at `address`.
+pub fn label(address: usize) -> String {
+    format!("L{}", address)
+}
+
+/// Readable listing, one instruction per line with a comment on what it does. Jump targets that
+/// land on an instruction get a label, the reserved combo operand is flagged.
+pub fn listing(program: &[i64]) -> String {
+    let items = disassemble(program);
+    let instruction_starts: HashSet<usize> = items
+        .iter()
+        .filter(|item| matches!(item, Item::Instruction(_)))
+        .map(Item::address)
+        .collect();
+    let labels: HashSet<usize> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Instruction(instruction) => instruction.jump_target(),
+            Item::Data { .. } => None,
+        })
+        .filter(|target| instruction_starts.contains(target))
+        .collect();
+
+    let mut lines = vec![];
+    for item in &items {
+        if labels.contains(&item.address()) {
+            lines.push(format!("{}:", label(item.address())));
+        }
+        let (code, comment) = match item {
+            Item::Instruction(instruction) => {
+                let operand = match (instruction.opcode, instruction.combo()) {
+                    (_, Some(combo)) => combo.to_string(),
+                    (Opcode::Jnz, _) if labels.contains(&(instruction.operand as usize)) => {
+                        label(instruction.operand as usize)
+                    }
+                    _ => instruction.operand.to_string(),
+                };
+                let mut comment = instruction.describe();
+                if instruction.uses_reserved_operand() {
+                    comment.push_str(", reserved combo operand 7");
+                }
+                if instruction.jump_target().is_some_and(|target| {
+                    !instruction_starts.contains(&target) && target < program.len()
+                }) {
+                    comment.push_str(", not the start of an instruction");
+                }
+                (
+                    format!("{} {}", instruction.opcode.mnemonic(), operand),
+                    comment,
+                )
+            }
+            Item::Data { values, .. } => (
+                format!(
+                    ".data {}",
+                    values
+                        .iter()
+                        .map(|value| value.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                "not an instruction".to_string(),
+            ),
+        };
+        lines.push(format!(
+            "    {:<12} ; {:>2}: {}",
+            code,
+            item.address(),
+            comment
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_the_example_program() {
+        assert_eq!(
+            listing(&[0, 1, 5, 4, 3, 0]),
+            "\
+L0:
+    adv 1        ;  0: A = A >> 1
+    out A        ;  2: output A & 7
+    jnz L0       ;  4: if A != 0 jump to 0"
+        );
+    }
+
+    #[test]
+    fn decodes_operands() {
+        let items = disassemble(&[2, 4, 1, 5, 7, 5, 4, 3, 6, 7, 3, 3, 5]);
+
+        let Item::Instruction(bst) = items[0] else {
+            panic!("expected an instruction");
+        };
+        assert_eq!(bst.combo(), Some(Combo::Register(Register::A)));
+        // bxl takes its operand literally, 5 is not register B here
+        let Item::Instruction(bxl) = items[1] else {
+            panic!("expected an instruction");
+        };
+        assert_eq!((bxl.combo(), bxl.operand), (None, 5));
+        let Item::Instruction(bdv) = items[4] else {
+            panic!("expected an instruction");
+        };
+        assert!(bdv.uses_reserved_operand());
+        assert_eq!(
+            items[6],
+            Item::Data {
+                address: 12,
+                values: vec![5]
+            }
+        );
+
+        let listing = listing(&[2, 4, 1, 5, 7, 5, 4, 3, 6, 7, 3, 3, 5]);
+        assert!(listing.contains("bdv 7        ;  8: B = A >> 7, reserved combo operand 7"));
+        assert!(listing.contains("jnz 3        ; 10: if A != 0 jump to 3, not the start"));
+        assert!(listing.ends_with(".data 5      ; 12: not an instruction"));
+    }
+}
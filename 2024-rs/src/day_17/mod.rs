@@ -1 +1,5 @@
+pub mod assembler;
+pub mod debugger;
+pub mod disassembler;
 pub mod solution;
+pub mod solver;
@@ -1,221 +1,458 @@
 use std::{
-    collections::{HashSet, VecDeque},
-    fs::read_to_string,
-    i64,
+    error::Error,
+    fmt::{self, Display},
 };
 
 use anyhow::{Context, Result};
 
+use super::{
+    debugger::{Step, Trace},
+    disassembler::{self, Combo, Opcode, Register},
+    solver::find_register_a,
+};
+use crate::{
+    params::{parse_value, unknown_param, Params, Preset},
+    parse::{self, ParseResult},
+    puzzle::{Answer, Solution},
+};
+
+/// Why a [`Computer`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// the pointer moved past the last instruction, the only normal way to stop
+    RanOffEnd,
+    /// the opcode at `pointer` is the last value of the program
+    TruncatedInstruction { pointer: usize },
+    /// the instruction at `pointer` uses the reserved combo operand 7
+    InvalidComboOperand { pointer: usize },
+    /// the value at `pointer` is not an opcode
+    InvalidOpcode { pointer: usize, value: i64 },
+    /// `budget` instructions ran without the program stopping
+    StepBudgetExceeded { budget: usize },
+}
+
+impl Halt {
+    /// `Ok` only for a normal halt.
+    pub fn into_result(self) -> Result<(), Halt> {
+        match self {
+            Halt::RanOffEnd => Ok(()),
+            halt => Err(halt),
+        }
+    }
+}
+
+impl Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Halt::RanOffEnd => write!(f, "ran off the end of the program"),
+            Halt::TruncatedInstruction { pointer } => {
+                write!(f, "instruction at {} has no operand", pointer)
+            }
+            Halt::InvalidComboOperand { pointer } => {
+                write!(
+                    f,
+                    "instruction at {} uses the reserved combo operand 7",
+                    pointer
+                )
+            }
+            Halt::InvalidOpcode { pointer, value } => {
+                write!(f, "invalid opcode {} at {}", value, pointer)
+            }
+            Halt::StepBudgetExceeded { budget } => {
+                write!(f, "still running after {} instructions", budget)
+            }
+        }
+    }
+}
+
+impl Error for Halt {}
+
 #[derive(Debug, Clone)]
-struct Computer {
-    registers: Vec<i64>,
+pub struct Computer {
+    registers: [i64; 3],
     program: Vec<i64>,
     pointer: usize,
     output: Vec<i64>,
+    /// instructions executed so far
+    steps: usize,
+    step_budget: Option<usize>,
 }
 
 impl Computer {
-    pub fn run(&mut self) {
-        while self.run_instruction() {}
+    /// `registers` holds A, B and C in that order, see [`Register::index`].
+    pub fn new(registers: [i64; 3], program: Vec<i64>) -> Self {
+        Computer {
+            registers,
+            program,
+            pointer: 0,
+            output: vec![],
+            steps: 0,
+            step_budget: None,
+        }
     }
 
-    fn run_instruction(&mut self) -> bool {
-        if self.pointer >= self.program.len() {
-            return false;
-        }
-        match self.get_opcode() {
-            0 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.registers[0] = self.registers[0] >> self.get_combo_operand();
-                self.pointer += 2;
-                true
-            }
-            1 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.registers[1] = self.registers[1] ^ self.get_literal_operand();
-                self.pointer += 2;
-                true
-            }
-            2 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.registers[1] = self.get_combo_operand() & 7;
-                self.pointer += 2;
-                true
-            }
-            3 => {
-                if self.registers[0] == 0 {
-                    // do nothing
-                    self.pointer += 2;
-                    return true;
-                }
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.pointer = self.get_literal_operand() as usize;
-                true
-            }
-            4 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.registers[1] = self.registers[1] ^ self.registers[2];
-                self.pointer += 2;
-                true
-            }
-            5 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.output.push(self.get_combo_operand() & 7);
-                self.pointer += 2;
-                true
-            }
-            6 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
-                }
-                self.registers[1] = self.registers[0] >> self.get_combo_operand();
-                self.pointer += 2;
-                true
+    pub fn registers(&self) -> &[i64; 3] {
+        &self.registers
+    }
+
+    pub fn set_register(&mut self, register: Register, value: i64) {
+        self.registers[register.index()] = value;
+    }
+
+    pub fn program(&self) -> &[i64] {
+        &self.program
+    }
+
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    /// Registers and program as source the assembler reads back, see [`disassembler::listing`].
+    pub fn disassemble(&self) -> String {
+        let registers: String = Register::ALL
+            .iter()
+            .zip(&self.registers)
+            .map(|(register, value)| format!(".register {} {}\n", register, value))
+            .collect();
+        registers + &disassembler::listing(&self.program)
+    }
+
+    pub fn pointer(&self) -> usize {
+        self.pointer
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Stops the program with [`Halt::StepBudgetExceeded`] once it executed `budget`
+    /// instructions, `None` lets it run forever.
+    pub fn set_step_budget(&mut self, budget: Option<usize>) {
+        self.step_budget = budget;
+    }
+
+    pub fn run(&mut self) -> Halt {
+        loop {
+            if let Err(halt) = self.run_instruction() {
+                return halt;
             }
-            7 => {
-                if self.pointer + 1 >= self.program.len() {
-                    return false;
+        }
+    }
+
+    /// Same as [`Computer::run`], recording every step on the way.
+    pub fn run_traced(&mut self) -> Trace {
+        let mut trace = Trace::default();
+        loop {
+            match self.step() {
+                Ok(step) => trace.steps.push(step),
+                Err(halt) => {
+                    trace.halt = Some(halt);
+                    return trace;
                 }
-                self.registers[2] = self.registers[0] >> self.get_combo_operand();
-                self.pointer += 2;
-                true
             }
-            _ => false,
         }
     }
 
-    fn get_opcode(&self) -> i64 {
-        self.program[self.pointer]
+    /// Runs a single instruction and reports what it did.
+    pub fn step(&mut self) -> Result<Step, Halt> {
+        let pointer = self.pointer;
+        let registers_before = self.registers;
+        let outputs = self.output.len();
+
+        let opcode = self.run_instruction()?;
+
+        Ok(Step {
+            pointer,
+            opcode,
+            operand: self.program[pointer + 1],
+            registers_before,
+            registers_after: self.registers,
+            output: self.output.get(outputs).copied(),
+        })
     }
 
-    fn get_combo_operand(&self) -> i64 {
-        let operand = self.program[self.pointer + 1];
-        match operand {
-            0..=3 => operand,
-            4 => self.registers[0],
-            5 => self.registers[1],
-            6 => self.registers[2],
-            _ => panic!("unexpected operand '{}'", operand),
+    /// Executes the instruction at the pointer, or tells why there is none to execute.
+    fn run_instruction(&mut self) -> Result<Opcode, Halt> {
+        let pointer = self.pointer;
+        let &value = self.program.get(pointer).ok_or(Halt::RanOffEnd)?;
+        if let Some(budget) = self.step_budget.filter(|&budget| self.steps >= budget) {
+            return Err(Halt::StepBudgetExceeded { budget });
         }
-    }
+        let opcode = Opcode::from_code(value).ok_or(Halt::InvalidOpcode { pointer, value })?;
+        let &operand = self
+            .program
+            .get(pointer + 1)
+            .ok_or(Halt::TruncatedInstruction { pointer })?;
+        let combo = match (opcode.takes_combo(), Combo::decode(operand)) {
+            (false, _) => 0,
+            (true, Combo::Literal(value)) => value,
+            (true, Combo::Register(register)) => self.registers[register.index()],
+            (true, Combo::Reserved) => return Err(Halt::InvalidComboOperand { pointer }),
+        };
 
-    fn get_literal_operand(&self) -> i64 {
-        self.program[self.pointer + 1]
+        let mut next = pointer + 2;
+        match opcode {
+            Opcode::Adv => self.registers[0] = shift(self.registers[0], combo),
+            Opcode::Bxl => self.registers[1] ^= operand,
+            Opcode::Bst => self.registers[1] = combo & 7,
+            Opcode::Jnz => {
+                if self.registers[0] != 0 {
+                    next = operand as usize;
+                }
+            }
+            Opcode::Bxc => self.registers[1] ^= self.registers[2],
+            Opcode::Out => self.output.push(combo & 7),
+            Opcode::Bdv => self.registers[1] = shift(self.registers[0], combo),
+            Opcode::Cdv => self.registers[2] = shift(self.registers[0], combo),
+        }
+        self.pointer = next;
+        self.steps += 1;
+
+        Ok(opcode)
     }
 }
 
-fn read_computer(file_name: &str) -> Result<Computer> {
-    let input = read_to_string(format!("./src/day_17/{}.txt", file_name))
-        .context("could not read input file")?;
-    let input_parts: Vec<&str> = input.split("\n\n").collect();
+/// `value >> amount`, shifting every bit out instead of overflowing for large amounts.
+fn shift(value: i64, amount: i64) -> i64 {
+    u32::try_from(amount)
+        .ok()
+        .and_then(|amount| value.checked_shr(amount))
+        .unwrap_or(if value < 0 { -1 } else { 0 })
+}
+
+fn read_computer(input: &str) -> Result<Computer> {
+    let [registers_part, program_part] = parse::sections_n(input)?;
+
+    let mut registers = [0; 3];
+    let mut lines = registers_part.lines();
+    for register in Register::ALL {
+        let line = lines
+            .next()
+            .context(format!("could not get register {} of input", register))?;
+        let (key, value) = line.key_value()?;
+        if key != format!("Register {}", register) {
+            return Err(line
+                .error(1, format!("expected register {}, got '{}'", register, key))
+                .into());
+        }
+        registers[register.index()] = line.parse(value)?;
+    }
+    if let Some(line) = lines.next() {
+        return Err(line.error(1, "expected only registers A, B and C").into());
+    }
 
-    let registers = input_parts
-        .get(0)
-        .context("could not get registers part of input")?
+    let program_line = program_part
         .lines()
-        .map(|register_line| {
-            register_line
-                .get(12..)
-                .context("could not get register value")?
-                .parse::<i64>()
-                .context(format!("could not parse register value"))
-        })
-        .collect::<Result<Vec<i64>>>()?;
-
-    let program = input_parts
-        .get(1)
-        .context("could not get program part of input")?
-        .get(9..)
-        .context("could not get program values")?
-        .trim()
+        .next()
+        .context("could not get program part of input")?;
+    let (key, values) = program_line.key_value()?;
+    if key != "Program" {
+        return Err(program_line
+            .error(1, format!("expected the program, got '{}'", key))
+            .into());
+    }
+    let program = values
         .split(",")
-        .map(|value| {
-            value
-                .parse::<i64>()
-                .context(format!("could not parse program value '{}'", value))
-        })
-        .collect::<Result<Vec<i64>>>()?;
-
-    Ok(Computer {
-        registers,
-        program,
-        pointer: 0,
-        output: vec![],
-    })
-}
+        .map(|value| program_line.parse::<i64>(value))
+        .collect::<ParseResult<Vec<i64>>>()?;
 
-fn part_one(original_computer: &Computer) {
-    let mut computer = original_computer.clone();
+    Ok(Computer::new(registers, program))
+}
 
-    computer.run();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day17Params {
+    /// instructions part one may run before it gives up on the program halting
+    pub step_budget: usize,
+}
 
-    let part_one: String = computer
-        .output
-        .iter()
-        .map(|value| value.to_string())
-        .collect::<Vec<_>>()
-        .join(",");
+impl Params for Day17Params {
+    fn preset(_: Preset) -> Self {
+        Day17Params {
+            step_budget: 1_000_000,
+        }
+    }
 
-    println!("part one result: {}", part_one);
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "step_budget" => self.step_budget = parse_value(key, value)?,
+            _ => return Err(unknown_param(key, &["step_budget"])),
+        }
+        Ok(())
+    }
 }
 
-fn part_two(original_computer: &Computer) {
-    let mut options: VecDeque<i64> = VecDeque::from_iter(0..1000);
-    let mut was: HashSet<i64> = HashSet::new();
+pub struct Day17;
 
-    let mut result: i64 = i64::MAX;
+impl Solution for Day17 {
+    type Input = Computer;
+    type Params = Day17Params;
 
-    while let Some(option) = options.pop_front() {
-        let mid = option * 8;
-        let (from, to) = (mid - 100, mid + 100);
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_computer(input)
+    }
 
-        for num in from.max(0)..to {
-            if was.contains(&num) {
-                continue;
-            }
-            let mut computer = original_computer.clone();
-
-            computer.registers[0] = num;
-            computer.run();
-
-            let output = computer.output;
-            let program: Vec<i64> = (0..output.len())
-                .map(|j| computer.program[computer.program.len() - j - 1])
-                .rev()
-                .collect();
-            let is_same = output.iter().zip(program.iter()).all(|(&a, &b)| a == b);
-
-            if is_same {
-                if output.len() < computer.program.len() {
-                    options.push_back(num);
-                    was.insert(num);
-                } else if output.len() == computer.program.len() {
-                    result = result.min(num);
-                }
-            }
-        }
+    fn part_one(original_computer: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        let mut computer = original_computer.clone();
+
+        computer.set_step_budget(Some(params.step_budget));
+        computer.run().into_result()?;
+
+        let part_one: String = computer
+            .output
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        Ok(part_one.into())
     }
 
-    println!("part two result: {}", result);
+    fn part_two(computer: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        Ok(find_register_a(computer, computer.program())?.into())
+    }
 }
 
-pub fn solve() -> Result<()> {
-    let computer = read_computer("input")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Register A: 729
+Register B: 0
+Register C: 0
 
-    part_one(&computer);
-    part_two(&computer);
+Program: 0,1,5,4,3,0
+";
 
-    Ok(())
+    const EXAMPLE_PART_TWO: &str = "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day17::part_one(&input, &Day17Params::preset(Preset::Example)).unwrap(),
+            Answer::Text("4,6,3,5,6,3,5,2,1,0".to_string())
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day17::parse(EXAMPLE_PART_TWO).unwrap();
+        assert_eq!(
+            Day17::part_two(&input, &Day17Params::preset(Preset::Example)).unwrap(),
+            Answer::from(117440)
+        );
+    }
+
+    #[test]
+    fn read_computer_example() {
+        let computer = read_computer(EXAMPLE).unwrap();
+        assert_eq!(computer.registers, [729, 0, 0]);
+        assert_eq!(computer.program, vec![0, 1, 5, 4, 3, 0]);
+        assert_eq!(computer.pointer, 0);
+        assert!(computer.output.is_empty());
+    }
+
+    #[test]
+    fn read_computer_rejects_malformed_input() {
+        assert!(read_computer("Register A: x\n\nProgram: 0,1").is_err());
+        assert!(read_computer("Register A: 1\n\nProgram: 0,a").is_err());
+        assert!(read_computer("Register A: 1").is_err());
+        assert_eq!(
+            read_computer("Register A: 1\n\nProgram: 1,2")
+                .unwrap_err()
+                .to_string(),
+            "could not get register B of input"
+        );
+        assert_eq!(
+            read_computer("Register A: 1\nRegister C: 2\nRegister B: 3\n\nProgram: 1,2")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected register B, got 'Register C'"
+        );
+        assert_eq!(
+            read_computer(
+                "Register A: 1\nRegister B: 2\nRegister C: 3\nRegister D: 4\n\nProgram: 1,2"
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 4, column 1: expected only registers A, B and C"
+        );
+        assert_eq!(
+            read_computer("Register A: 1\nRegister B: 2\nRegister C: 3\n\nFoo: 1,2")
+                .unwrap_err()
+                .to_string(),
+            "line 5, column 1: expected the program, got 'Foo'"
+        );
+        assert_eq!(
+            read_computer("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,a")
+                .unwrap_err()
+                .to_string(),
+            "line 5, column 12: could not parse 'a'"
+        );
+    }
+
+    #[test]
+    fn run_reports_why_it_halted() {
+        let run = |program: &str, budget: Option<usize>| {
+            let mut computer = read_computer(&format!(
+                "Register A: 5\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+                program
+            ))
+            .unwrap();
+            computer.set_step_budget(budget);
+            (computer.run(), computer.steps())
+        };
+
+        assert_eq!(run("5,4", None), (Halt::RanOffEnd, 1));
+        assert_eq!(
+            run("5,4,5", None),
+            (Halt::TruncatedInstruction { pointer: 2 }, 1)
+        );
+        assert_eq!(
+            run("1,2,6,7", None),
+            (Halt::InvalidComboOperand { pointer: 2 }, 1)
+        );
+        assert_eq!(
+            run("9,0", None),
+            (
+                Halt::InvalidOpcode {
+                    pointer: 0,
+                    value: 9
+                },
+                0
+            )
+        );
+        // jumps back to the start forever, A never changes
+        assert_eq!(
+            run("5,4,3,0", Some(1000)),
+            (Halt::StepBudgetExceeded { budget: 1000 }, 1000)
+        );
+        assert_eq!(
+            run("5,4,3,0", Some(1000)).0.to_string(),
+            "still running after 1000 instructions"
+        );
+        assert!(run("5,4,3,0", Some(10)).0.into_result().is_err());
+
+        let looping = Computer::new([1, 0, 0], vec![3, 0]);
+        assert_eq!(
+            Day17::part_one(&looping, &Day17Params { step_budget: 5 })
+                .unwrap_err()
+                .to_string(),
+            "still running after 5 instructions"
+        );
+    }
+
+    #[test]
+    fn large_shifts_clear_the_register() {
+        assert_eq!(shift(1 << 40, 100), 0);
+        assert_eq!(shift(-8, 100), -1);
+        assert_eq!(shift(64, 3), 8);
+    }
 }
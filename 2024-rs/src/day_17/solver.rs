@@ -0,0 +1,279 @@
+use anyhow::{anyhow, bail, Result};
+
+use super::{
+    disassembler::{self, Combo, Instruction, Item, Opcode, Register},
+    solution::{Computer, Halt},
+};
+
+/// Instructions the check of a found register A may run.
+const CHECK_STEP_BUDGET: usize = 100_000;
+
+/// Loop body of a program that shifts A by three bits and prints one value per iteration, so
+/// every output depends only on the octal digits of A from its own position upwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitLoop {
+    /// every instruction but the final `jnz 0`
+    body: Vec<i64>,
+}
+
+impl DigitLoop {
+    /// Checks the program is a single `jnz 0` loop with one `adv 3`, one `out`, and no state in
+    /// B or C carried over from the previous iteration.
+    pub fn analyze(program: &[i64]) -> Result<DigitLoop> {
+        let mut instructions: Vec<Instruction> = vec![];
+        for item in disassembler::disassemble(program) {
+            match item {
+                Item::Instruction(instruction) => instructions.push(instruction),
+                Item::Data { address, .. } => {
+                    bail!("value at {} is not an instruction", address)
+                }
+            }
+        }
+
+        let Some((last, body)) = instructions.split_last() else {
+            bail!("program is empty");
+        };
+        if last.jump_target() != Some(0) {
+            bail!("program does not end with a 'jnz 0' loop");
+        }
+
+        let mut shifts = 0;
+        let mut outputs = 0;
+        let mut written = [false; 3];
+        for instruction in body {
+            let address = instruction.address;
+            let mut reads = vec![];
+            match instruction.combo() {
+                Some(Combo::Reserved) => {
+                    bail!(
+                        "instruction at {} uses the reserved combo operand 7",
+                        address
+                    )
+                }
+                Some(Combo::Register(register)) => reads.push(register),
+                _ => {}
+            }
+
+            let writes = match instruction.opcode {
+                Opcode::Adv => {
+                    if instruction.combo() != Some(Combo::Literal(3)) {
+                        bail!(
+                            "'adv' at {} shifts A by {} instead of 3 bits",
+                            address,
+                            Combo::decode(instruction.operand)
+                        );
+                    }
+                    shifts += 1;
+                    None
+                }
+                Opcode::Jnz => bail!("jump at {} inside the loop", address),
+                Opcode::Out => {
+                    outputs += 1;
+                    None
+                }
+                Opcode::Bxl => {
+                    reads.push(Register::B);
+                    Some(Register::B)
+                }
+                Opcode::Bxc => {
+                    reads.extend([Register::B, Register::C]);
+                    Some(Register::B)
+                }
+                Opcode::Bst | Opcode::Bdv => Some(Register::B),
+                Opcode::Cdv => Some(Register::C),
+            };
+
+            if let Some(register) = reads
+                .into_iter()
+                .find(|&register| register != Register::A && !written[register.index()])
+            {
+                bail!(
+                    "register {} is read at {} before the loop writes it, so outputs depend on \
+                     earlier iterations",
+                    register,
+                    address
+                );
+            }
+            if let Some(register) = writes {
+                written[register.index()] = true;
+            }
+        }
+
+        if shifts != 1 {
+            bail!("loop needs exactly one 'adv 3', found {}", shifts);
+        }
+        if outputs != 1 {
+            bail!("loop needs exactly one 'out', found {}", outputs);
+        }
+
+        Ok(DigitLoop {
+            body: program[..last.address].to_vec(),
+        })
+    }
+
+    /// What one iteration prints when it starts with `a` in register A.
+    pub fn output_for(&self, a: i64) -> Result<i64> {
+        let mut computer = Computer::new([a, 0, 0], self.body.clone());
+        computer.run().into_result()?;
+        computer
+            .output()
+            .first()
+            .copied()
+            .ok_or_else(|| anyhow!("loop body printed nothing"))
+    }
+
+    /// Smallest A printing `target`, picking octal digits from the most significant one down.
+    /// Every candidate has one digit per output, so the first one found is the smallest.
+    pub fn solve(&self, target: &[i64]) -> Result<Option<i64>> {
+        if target.is_empty() {
+            bail!("the loop prints at least one value");
+        }
+        if target.len() > 21 {
+            bail!(
+                "{} outputs need {} bits of A, more than fit in 63",
+                target.len(),
+                target.len() * 3
+            );
+        }
+        self.search(target, target.len(), 0)
+    }
+
+    /// `prefix` holds the digits of A above position `digits`.
+    fn search(&self, target: &[i64], digits: usize, prefix: i64) -> Result<Option<i64>> {
+        let Some(position) = digits.checked_sub(1) else {
+            return Ok(Some(prefix));
+        };
+        // a zero top digit would end the loop one output early, unless A is a single digit
+        let lowest = if prefix == 0 && target.len() > 1 {
+            1
+        } else {
+            0
+        };
+
+        for digit in lowest..8 {
+            let a = prefix * 8 + digit;
+            if self.output_for(a)? != target[position] {
+                continue;
+            }
+            if let Some(found) = self.search(target, position, a)? {
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Smallest value of register A that makes `computer` print exactly `target`, for programs
+/// [`DigitLoop::analyze`] accepts.
+pub fn find_register_a(computer: &Computer, target: &[i64]) -> Result<i64> {
+    let digit_loop = DigitLoop::analyze(computer.program())
+        .map_err(|error| anyhow!("unsupported program: {}", error))?;
+    let a = digit_loop
+        .solve(target)?
+        .ok_or_else(|| anyhow!("no value of register A prints {:?}", target))?;
+
+    let mut check = computer.clone();
+    check.set_register(Register::A, a);
+    check.set_step_budget(Some(CHECK_STEP_BUDGET));
+    match check.run() {
+        Halt::RanOffEnd if check.output() == target => Ok(a),
+        halt => Err(anyhow!(
+            "register A {} was expected to print {:?}, but printed {:?} and stopped: {}",
+            a,
+            target,
+            check.output(),
+            halt
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        day_17::{assembler::assemble, solution::Day17},
+        puzzle::Solution,
+    };
+
+    fn computer(source: &str) -> Computer {
+        Day17::parse(&assemble(source).unwrap().to_string()).unwrap()
+    }
+
+    #[test]
+    fn solves_arbitrary_targets() {
+        // shaped like the real inputs, with B and C depending on the higher digits of A
+        let program = computer(
+            "\
+            loop:
+                bst A
+                bxl 5
+                cdv B
+                bxl 6
+                adv 3
+                bxc
+                out B
+                jnz loop",
+        );
+
+        for target in [vec![2, 4, 1, 5], vec![0], vec![3, 2, 5, 1, 1, 1, 7, 1, 5]] {
+            let a = find_register_a(&program, &target).unwrap();
+
+            let mut check = program.clone();
+            check.set_register(Register::A, a);
+            check.run();
+            assert_eq!(check.output(), target);
+
+            // nothing smaller prints the same
+            assert!((0..a.min(5000)).all(|smaller| {
+                let mut check = program.clone();
+                check.set_register(Register::A, smaller);
+                check.run();
+                check.output() != target
+            }));
+        }
+        assert_eq!(find_register_a(&program, &[2, 4, 1, 5]).unwrap(), 2203);
+    }
+
+    #[test]
+    fn explains_unsupported_programs() {
+        let error = |source: &str| {
+            find_register_a(&computer(source), &[0])
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error("l: adv 1\nout A\njnz l"),
+            "unsupported program: 'adv' at 0 shifts A by 1 instead of 3 bits"
+        );
+        assert_eq!(
+            error("l: adv 3\nout B\njnz l"),
+            "unsupported program: register B is read at 2 before the loop writes it, so \
+             outputs depend on earlier iterations"
+        );
+        assert_eq!(
+            error("adv 3\nout A\nout A\njnz 0"),
+            "unsupported program: loop needs exactly one 'out', found 2"
+        );
+        assert_eq!(
+            error("adv 3\nout A"),
+            "unsupported program: program does not end with a 'jnz 0' loop"
+        );
+        assert_eq!(
+            error("adv 3\nout 7\njnz 0"),
+            "unsupported program: instruction at 2 uses the reserved combo operand 7"
+        );
+    }
+
+    #[test]
+    fn impossible_targets_are_errors() {
+        let program = computer("l: adv 3\nout A\njnz l");
+        assert!(find_register_a(&program, &[1, 2, 0]).is_ok());
+        // A is shifted before it is printed, so the last output is always 0
+        assert_eq!(
+            find_register_a(&program, &[2, 1]).unwrap_err().to_string(),
+            "no value of register A prints [2, 1]"
+        );
+        assert!(find_register_a(&program, &[0; 22]).is_err());
+    }
+}
@@ -1,111 +1,299 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs::read_to_string,
-    usize,
-};
+use anyhow::{anyhow, bail, Context, Result};
 
-use anyhow::{anyhow, Context, Result};
+use crate::{
+    grid::{Grid, Pos},
+    params::{parse_value, unknown_param, Params, Preset},
+    point::Point,
+    puzzle::{Answer, Solution},
+    search::bfs,
+    union_find::UnionFind,
+};
 use itertools::Itertools;
 
-fn read_coordinates(file_name: &str) -> Result<Vec<(usize, usize)>> {
-    let input =
-        read_to_string(format!("./src/day_18/{}.txt", file_name)).context("could not read file")?;
+fn read_coordinates(input: &str) -> Result<Vec<Point>> {
     input
         .lines()
         .map(|line| {
             let (x, y) = line
                 .split(',')
-                .rev()
                 .map(str::parse)
                 .collect_tuple()
                 .ok_or_else(|| anyhow!("invalid pair"))?;
-            Ok((x?, y?))
+            Ok(Point::new(x?, y?))
         })
         .collect()
 }
 
-fn shortest_path(
-    corrupted_coords: &HashSet<(usize, usize)>,
-    end_point: (usize, usize),
-) -> Result<i32> {
-    let start_point: (usize, usize) = (0, 0);
-
-    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
-    let mut dist: HashMap<(usize, usize), i32> = HashMap::new();
-
-    let dxdy: Vec<(i32, i32)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
-
-    dist.insert(start_point, 0);
-    queue.push_back(start_point);
-
-    while let Some(top) = queue.pop_front() {
-        let (i, j) = top;
-        let cost = *dist.get(&top).expect("should have dist already");
-
-        dxdy.iter()
-            .map(|&(di, dj)| (i as i32 + di, j as i32 + dj))
-            .filter(|&(ni, nj)| {
-                ni >= 0 && ni <= end_point.0 as i32 && nj >= 0 && nj <= end_point.1 as i32
-            })
-            .for_each(|(ni, nj)| {
-                let neighbour = (ni as usize, nj as usize);
-                if dist.contains_key(&neighbour) || corrupted_coords.contains(&neighbour) {
-                    return;
-                }
-                dist.insert(neighbour, cost + 1);
-                queue.push_back(neighbour);
-            });
+/// Memory space spanning `0,0` to `end_point` with the given bytes marked as corrupted.
+fn corrupted_grid(coords: &[Point], end_point: Pos) -> Grid<bool> {
+    let mut grid = Grid::new(end_point.1 + 1, end_point.0 + 1, false);
+    coords.iter().for_each(|point| {
+        if let Some(cell) = point.to_pos().and_then(|pos| grid.get_mut(pos)) {
+            *cell = true;
+        }
+    });
+    grid
+}
+
+fn shortest_path(corrupted: &Grid<bool>, end_point: Pos) -> Result<u64> {
+    if corrupted[(0, 0)] {
+        bail!("no path found, the start is corrupted");
     }
+    let search = bfs(
+        (0, 0),
+        |&pos| corrupted.neighbours4(pos).filter(|&next| !corrupted[next]),
+        |&pos| pos == end_point,
+    );
 
-    dist.get(&end_point).context("no path found").copied()
+    search.cost().context("no path found")
 }
 
-fn part_one() -> Result<()> {
-    let end_point: (usize, usize) = (70, 70);
-    let input_coords = read_coordinates("input")?;
-    let coords = &input_coords[0..1024];
+/// Index of the first byte after which the exit can no longer be reached, binary searching
+/// over the number of fallen bytes with a full search per probe.
+fn first_cutting_byte_by_search(coords: &[Point], end_point: Pos) -> Option<usize> {
+    let blocks_exit = |count: usize| {
+        let corrupted = corrupted_grid(&coords[0..count], end_point);
+        shortest_path(&corrupted, end_point).is_err()
+    };
 
-    let corrupted_coords: HashSet<(usize, usize)> =
-        HashSet::from_iter(coords.iter().map(|coord| *coord));
-    let result = shortest_path(&corrupted_coords, end_point)?;
+    // smallest prefix length that cuts off the exit
+    let mut l = 1;
+    let mut r = coords.len();
 
-    println!("part one result: {}", result);
+    while l < r {
+        let mid = (l + r) >> 1;
+        if blocks_exit(mid) {
+            r = mid;
+        } else {
+            l = mid + 1;
+        }
+    }
 
-    Ok(())
+    (!coords.is_empty() && blocks_exit(l)).then(|| l - 1)
 }
 
-fn part_two() -> Result<()> {
-    let end_point: (usize, usize) = (70, 70);
-    let input_coords = read_coordinates("input")?;
+/// Same as [`first_cutting_byte_by_search`], but lets every byte fall and then takes them away
+/// again from the last one, joining the cells they free up until the exit is connected.
+fn first_cutting_byte(coords: &[Point], end_point: Pos) -> Option<usize> {
+    let index = |(row, col): Pos| row * (end_point.1 + 1) + col;
 
-    let mut l = 0;
-    let mut r = input_coords.len() - 1;
-    let mut result: Option<(usize, usize)> = None;
+    // a cell frees up only once the first byte that fell on it is taken away
+    let mut first_fall: Grid<Option<usize>> = Grid::new(end_point.1 + 1, end_point.0 + 1, None);
+    for (i, point) in coords.iter().enumerate() {
+        if let Some(cell) = point.to_pos().and_then(|pos| first_fall.get_mut(pos)) {
+            cell.get_or_insert(i);
+        }
+    }
 
-    while l < r {
-        let mid = (l + r) >> 1;
-        let corrupted_coords: HashSet<(usize, usize)> =
-            HashSet::from_iter(input_coords[0..mid + 1].iter().map(|coord| *coord));
+    let mut sets = UnionFind::new(first_fall.width() * first_fall.height());
+    let join = |sets: &mut UnionFind, first_fall: &Grid<Option<usize>>, pos: Pos| {
+        for next in first_fall.neighbours4(pos) {
+            if first_fall[next].is_none() {
+                sets.union(index(pos), index(next));
+            }
+        }
+    };
 
-        if shortest_path(&corrupted_coords, end_point).is_err() {
-            r = mid - 1;
-            result = Some(input_coords[mid]);
-        } else {
-            l = mid + 1;
+    for pos in first_fall.positions() {
+        if first_fall[pos].is_none() {
+            join(&mut sets, &first_fall, pos);
         }
     }
+    if sets.connected(index((0, 0)), index(end_point)) {
+        return None;
+    }
 
-    if let Some(result) = result {
-        // reverse since we reversed when reading the input
-        println!("part two result: {:?}", (result.1, result.0));
-    } else {
-        println!("part two: no answer");
+    for (i, point) in coords.iter().enumerate().rev() {
+        let Some(pos) = point
+            .to_pos()
+            .filter(|&pos| first_fall.get(pos) == Some(&Some(i)))
+        else {
+            continue;
+        };
+        first_fall[pos] = None;
+        join(&mut sets, &first_fall, pos);
+        if first_fall[(0, 0)].is_none()
+            && first_fall[end_point].is_none()
+            && sets.connected(index((0, 0)), index(end_point))
+        {
+            return Some(i);
+        }
     }
 
-    Ok(())
+    None
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day18Params {
+    /// exit in the bottom right corner, also the largest coordinate of the memory space
+    pub end_x: usize,
+    pub end_y: usize,
+    /// how many bytes have fallen in part one
+    pub bytes: usize,
+    /// part two takes bytes away with union-find instead of binary searching with full searches
+    pub incremental: bool,
 }
 
-pub fn solve() -> Result<()> {
-    part_one()?;
-    part_two()
+impl Params for Day18Params {
+    fn preset(preset: Preset) -> Self {
+        match preset {
+            Preset::Example => Day18Params {
+                end_x: 6,
+                end_y: 6,
+                bytes: 12,
+                incremental: true,
+            },
+            Preset::Real => Day18Params {
+                end_x: 70,
+                end_y: 70,
+                bytes: 1024,
+                incremental: true,
+            },
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "end_x" => self.end_x = parse_value(key, value)?,
+            "end_y" => self.end_y = parse_value(key, value)?,
+            "bytes" => self.bytes = parse_value(key, value)?,
+            "incremental" => self.incremental = parse_value(key, value)?,
+            _ => {
+                return Err(unknown_param(
+                    key,
+                    &["end_x", "end_y", "bytes", "incremental"],
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Day18Params {
+    /// as a `(row, column)` grid position
+    fn end_point(&self) -> Pos {
+        (self.end_y, self.end_x)
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Point>;
+    type Params = Day18Params;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_coordinates(input)
+    }
+
+    fn part_one(input_coords: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        let end_point = params.end_point();
+        let coords = input_coords.get(0..params.bytes).context(format!(
+            "expected at least {} bytes, got {}",
+            params.bytes,
+            input_coords.len()
+        ))?;
+
+        let result = shortest_path(&corrupted_grid(coords, end_point), end_point)?;
+
+        Ok(result.into())
+    }
+
+    fn part_two(input_coords: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        let end_point = params.end_point();
+
+        let first_cutting = if params.incremental {
+            first_cutting_byte(input_coords, end_point)
+        } else {
+            first_cutting_byte_by_search(input_coords, end_point)
+        };
+
+        let byte = first_cutting.context("no byte cuts off the exit")?;
+        Ok(input_coords[byte].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day18::part_one(&input, &Day18Params::preset(Preset::Example)).unwrap(),
+            Answer::from(22)
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day18::part_two(&input, &Day18Params::preset(Preset::Example)).unwrap(),
+            Answer::Coordinate(6, 1)
+        );
+    }
+
+    #[test]
+    fn both_part_two_modes_agree() {
+        let input = Day18::parse(EXAMPLE).unwrap();
+        let end_point = Day18Params::preset(Preset::Example).end_point();
+        assert_eq!(first_cutting_byte(&input, end_point), Some(20));
+        assert_eq!(first_cutting_byte_by_search(&input, end_point), Some(20));
+
+        // the exit itself falls last, or nothing ever cuts it off
+        let mut exit_last = input[..20].to_vec();
+        exit_last.push(Point::new(6, 6));
+        assert_eq!(first_cutting_byte(&exit_last, end_point), Some(20));
+        assert_eq!(
+            first_cutting_byte_by_search(&exit_last, end_point),
+            Some(20)
+        );
+        // so does the start
+        let mut start_last = input[..5].to_vec();
+        start_last.push(Point::new(0, 0));
+        assert_eq!(first_cutting_byte(&start_last, end_point), Some(5));
+        assert_eq!(
+            first_cutting_byte_by_search(&start_last, end_point),
+            Some(5)
+        );
+        assert_eq!(first_cutting_byte(&input[..20], end_point), None);
+        assert_eq!(first_cutting_byte(&[], end_point), None);
+
+        let mut params = Day18Params::preset(Preset::Example);
+        params.set("incremental", "false").unwrap();
+        assert_eq!(
+            Day18::part_two(&input, &params).unwrap(),
+            Answer::Coordinate(6, 1)
+        );
+    }
 }
@@ -1,57 +1,49 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
-};
+use std::collections::HashSet;
+
+use anyhow::{Ok, Result};
 
-use anyhow::{anyhow, Context, Ok, Result};
+use crate::{
+    cell::CharCell,
+    char_cell,
+    memo::Memo,
+    parse,
+    puzzle::{Answer, Solution},
+};
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-enum Color {
-    White,
-    Blue,
-    Black,
-    Red,
-    Green,
+char_cell! {
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    pub enum Color: "color" {
+        White = 'w',
+        Blue = 'u',
+        Black = 'b',
+        Red = 'r',
+        Green = 'g',
+    }
 }
 
 type Pattern = Vec<Color>;
 
 #[derive(Debug)]
-struct InputData {
+pub struct InputData {
     allowed_patterns: HashSet<Pattern>,
     designs: Vec<Pattern>,
 }
 
 fn string_to_pattern(pattern_str: &str) -> Result<Pattern> {
-    pattern_str
-        .chars()
-        .map(|color| match color {
-            'w' => Ok(Color::White),
-            'u' => Ok(Color::Blue),
-            'b' => Ok(Color::Black),
-            'r' => Ok(Color::Red),
-            'g' => Ok(Color::Green),
-            _ => Err(anyhow!("invalid color '{}'", color)),
-        })
-        .collect()
+    pattern_str.chars().map(Color::parse_char).collect()
 }
 
-fn read_input(file_name: &str) -> Result<InputData> {
-    let input =
-        read_to_string(format!("./src/day_19/{}.txt", file_name)).context("could not read file")?;
+fn read_input(input: &str) -> Result<InputData> {
+    let [patterns_part, designs_part] = parse::sections_n(input)?;
 
-    let mut input_parts = input.split("\n\n");
-
-    let allowed_patterns = input_parts
-        .next()
-        .context("could not read allowed patterns")?
+    let allowed_patterns = patterns_part
+        .text
         .split(", ")
         .map(string_to_pattern)
         .collect::<Result<_>>()?;
 
-    let designs = input_parts
-        .next()
-        .context("could not read designs")?
+    let designs = designs_part
+        .text
         .lines()
         .map(string_to_pattern)
         .collect::<Result<_>>()?;
@@ -62,12 +54,12 @@ fn read_input(file_name: &str) -> Result<InputData> {
     })
 }
 
-pub fn solve() -> Result<()> {
+fn count_possible_ways_per_design(input: &InputData) -> Vec<u64> {
     fn count_possible_ways_for_design(
         design: &Pattern,
         index: usize,
         allowed_patterns: &HashSet<Pattern>,
-        cache: &mut HashMap<usize, u64>,
+        cache: &mut Memo<usize, u64>,
     ) -> u64 {
         let len = design.len();
 
@@ -75,48 +67,109 @@ pub fn solve() -> Result<()> {
             return 1;
         }
 
-        if let Some(cached_result) = cache.get(&index) {
-            return *cached_result;
-        }
-
-        let result = allowed_patterns
-            .iter()
-            .filter(|pattern| pattern.len() + index <= len)
-            .filter(|pattern| (0..pattern.len()).all(|i| pattern[i] == design[index + i]))
-            .map(|pattern| {
-                count_possible_ways_for_design(
-                    design,
-                    index + pattern.len(),
-                    allowed_patterns,
-                    cache,
-                )
-            })
-            .sum();
-
-        cache.insert(index, result);
-
-        result
+        cache.get_or_insert_with(index, |cache| {
+            allowed_patterns
+                .iter()
+                .filter(|pattern| pattern.len() + index <= len)
+                .filter(|pattern| (0..pattern.len()).all(|i| pattern[i] == design[index + i]))
+                .map(|pattern| {
+                    count_possible_ways_for_design(
+                        design,
+                        index + pattern.len(),
+                        allowed_patterns,
+                        cache,
+                    )
+                })
+                .sum()
+        })
     }
 
     let InputData {
         allowed_patterns,
         designs,
-    } = read_input("input")?;
+    } = input;
 
-    let results: Vec<u64> = designs
+    // indexes only make sense within one design
+    let mut cache = Memo::new();
+
+    designs
         .iter()
         .map(|design| {
-            let mut cache = HashMap::new();
-            count_possible_ways_for_design(design, 0, &allowed_patterns, &mut cache)
+            cache.clear();
+            count_possible_ways_for_design(design, 0, allowed_patterns, &mut cache)
         })
-        .collect();
+        .collect()
+}
+
+pub struct Day19;
 
-    println!(
-        "part one result: {}",
-        results.iter().filter(|&result| *result > 0).count()
-    );
+impl Solution for Day19 {
+    type Input = InputData;
+    type Params = ();
 
-    println!("part two result: {}", results.iter().sum::<u64>());
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input)
+    }
 
-    Ok(())
+    fn part_one(input: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let results = count_possible_ways_per_design(input);
+
+        Ok(results.iter().filter(|&result| *result > 0).count().into())
+    }
+
+    fn part_two(input: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let results = count_possible_ways_per_design(input);
+
+        Ok(results.iter().sum::<u64>().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part_one(&input, &()).unwrap(), Answer::from(6));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part_two(&input, &()).unwrap(), Answer::from(16));
+    }
+
+    #[test]
+    fn string_to_pattern_maps_every_color() {
+        assert_eq!(
+            string_to_pattern("wubrg").unwrap(),
+            vec![
+                Color::White,
+                Color::Blue,
+                Color::Black,
+                Color::Red,
+                Color::Green
+            ]
+        );
+        assert!(string_to_pattern("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn string_to_pattern_rejects_unknown_colors() {
+        assert!(string_to_pattern("wx").is_err());
+    }
 }
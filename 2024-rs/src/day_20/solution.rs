@@ -1,126 +1,63 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs::read_to_string,
-    u32,
-};
+use std::collections::HashMap;
 
-use anyhow::{anyhow, Context, Ok, Result};
+use anyhow::{Context, Ok, Result};
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-enum MazeCell {
-    Empty,
-    Wall,
-    Start,
-    Finish,
-}
+use crate::{
+    char_cell,
+    grid::{Grid, Pos},
+    params::{parse_value, unknown_param, Params, Preset},
+    puzzle::{Answer, Solution},
+    search::bfs,
+};
 
-fn read_maze(file_name: &str) -> Result<Vec<Vec<MazeCell>>> {
-    let input =
-        read_to_string(format!("./src/day_20/{}.txt", file_name)).context("could not read file")?;
-
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|cell| match cell {
-                    '.' => Ok(MazeCell::Empty),
-                    '#' => Ok(MazeCell::Wall),
-                    'S' => Ok(MazeCell::Start),
-                    'E' => Ok(MazeCell::Finish),
-                    _ => Err(anyhow!("unknown maze cell '{}'", cell)),
-                })
-                .collect()
-        })
-        .collect()
+char_cell! {
+    #[derive(Debug, Hash, PartialEq, Eq)]
+    pub enum MazeCell: "maze cell" {
+        Empty = '.',
+        Wall = '#',
+        Start = 'S',
+        Finish = 'E',
+    }
 }
 
-fn get_neighbours((i, j): (usize, usize), (n, m): (usize, usize)) -> Vec<(usize, usize)> {
-    vec![
-        (i, j.wrapping_sub(1)),
-        (i, j + 1),
-        (i.wrapping_sub(1), j),
-        (i + 1, j),
-    ]
-    .into_iter()
-    .filter(|&(ni, nj)| ni < n && nj < m)
-    .collect()
+fn read_maze(input: &str) -> Result<Grid<MazeCell>> {
+    Grid::parse_cells(input)
 }
 
-fn shortest_path_distances(
-    walls: &HashSet<(usize, usize)>,
-    start: (usize, usize),
-    finish: (usize, usize),
-    dimensions: (usize, usize),
-) -> HashMap<(usize, usize), u32> {
-    let (n, m) = dimensions;
-
-    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
-    let mut dist = HashMap::new();
-
-    queue.push_back(start);
-    dist.insert(start, 0);
-
-    while let Some(top) = queue.pop_front() {
-        if top == finish {
-            break;
-        }
-
-        get_neighbours(top, (n, m))
-            .into_iter()
-            .filter(|neighbour| !walls.contains(neighbour))
-            .for_each(|(ni, nj)| {
-                if dist.contains_key(&(ni, nj)) {
-                    return;
-                }
-                let cost = dist.get(&top).expect("should have distance once here");
-                dist.insert((ni, nj), *cost + 1);
-                queue.push_back((ni, nj));
-            });
-    }
-
-    dist
+fn shortest_path_distances(maze: &Grid<MazeCell>, start: Pos, finish: Pos) -> HashMap<Pos, u64> {
+    let search = bfs(
+        start,
+        |&pos| {
+            maze.neighbours4(pos)
+                .filter(|&next| maze[next] != MazeCell::Wall)
+        },
+        |&pos| pos == finish,
+    );
+
+    search.into_distances()
 }
 
-fn manhattan_dist(from: (usize, usize), to: (usize, usize)) -> u32 {
+fn manhattan_dist(from: Pos, to: Pos) -> u64 {
     let x = from.0.max(to.0) - from.0.min(to.0);
     let y = from.1.max(to.1) - from.1.min(to.1);
-    (x + y) as u32
+    (x + y) as u64
 }
 
-pub fn solve() -> Result<()> {
-    let max_cheat = 20;
-    let diff = 100;
-    let maze = read_maze("input")?;
-
-    let (n, m) = (maze.len(), maze[0].len());
-
-    let mut start = Default::default();
-    let mut finish = Default::default();
-
-    let mut walls: HashSet<(usize, usize)> = HashSet::new();
-    let mut empties = vec![];
-
-    for i in 0..n {
-        for j in 0..m {
-            match maze[i][j] {
-                MazeCell::Start => {
-                    start = (i, j);
-                    empties.push((i, j));
-                }
-                MazeCell::Finish => {
-                    finish = (i, j);
-                    empties.push((i, j));
-                }
-                MazeCell::Wall => {
-                    walls.insert((i, j));
-                }
-                MazeCell::Empty => {
-                    empties.push((i, j));
-                }
-            };
-        }
-    }
-    let dist_from_start = shortest_path_distances(&walls, start, finish, (n, m));
+fn count_cheats(maze: &Grid<MazeCell>, max_cheat: u64, diff: u64) -> Result<usize> {
+    let start = maze
+        .position_of(&MazeCell::Start)
+        .context("maze has no start point")?;
+    let finish = maze
+        .position_of(&MazeCell::Finish)
+        .context("maze has no end point")?;
+
+    let empties: Vec<Pos> = maze
+        .iter()
+        .filter(|(_, cell)| **cell != MazeCell::Wall)
+        .map(|(pos, _)| pos)
+        .collect();
+
+    let dist_from_start = shortest_path_distances(maze, start, finish);
     let mut result = 0;
 
     for i in 0..empties.len() {
@@ -130,8 +67,12 @@ pub fn solve() -> Result<()> {
             }
             let from = empties[i];
             let to = empties[j];
-            let dist_to_from = *dist_from_start.get(&from).unwrap();
-            let dist_to_to = *dist_from_start.get(&to).unwrap();
+            let (Some(&dist_to_from), Some(&dist_to_to)) =
+                (dist_from_start.get(&from), dist_from_start.get(&to))
+            else {
+                // cells the track never reaches cannot be part of a cheat
+                continue;
+            };
             let cheat = manhattan_dist(from, to);
 
             if cheat > max_cheat {
@@ -150,7 +91,114 @@ pub fn solve() -> Result<()> {
         }
     }
 
-    println!("result: {}", result);
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day20Params {
+    /// longest cheat allowed in part one and part two, in picoseconds
+    pub part_one_cheat: u64,
+    pub part_two_cheat: u64,
+    /// only cheats saving at least this many picoseconds are counted
+    pub min_saving: u64,
+}
 
-    Ok(())
+impl Params for Day20Params {
+    fn preset(preset: Preset) -> Self {
+        Day20Params {
+            part_one_cheat: 2,
+            part_two_cheat: 20,
+            min_saving: match preset {
+                Preset::Example => 50,
+                Preset::Real => 100,
+            },
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "part_one_cheat" => self.part_one_cheat = parse_value(key, value)?,
+            "part_two_cheat" => self.part_two_cheat = parse_value(key, value)?,
+            "min_saving" => self.min_saving = parse_value(key, value)?,
+            _ => {
+                return Err(unknown_param(
+                    key,
+                    &["part_one_cheat", "part_two_cheat", "min_saving"],
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Grid<MazeCell>;
+    type Params = Day20Params;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_maze(input)
+    }
+
+    fn part_one(maze: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        Ok(count_cheats(maze, params.part_one_cheat, params.min_saving)?.into())
+    }
+
+    fn part_two(maze: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        Ok(count_cheats(maze, params.part_two_cheat, params.min_saving)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day20::part_one(&input, &Day20Params::preset(Preset::Example)).unwrap(),
+            Answer::from(1)
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day20::part_two(&input, &Day20Params::preset(Preset::Example)).unwrap(),
+            Answer::from(285)
+        );
+    }
+
+    #[test]
+    fn mazes_need_a_start_and_an_end() {
+        let params = Day20Params::preset(Preset::Example);
+        let error = |input: &str| {
+            Day20::part_one(&Day20::parse(input).unwrap(), &params)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(error(""), "maze has no start point");
+        assert_eq!(error("#S.#"), "maze has no end point");
+    }
 }
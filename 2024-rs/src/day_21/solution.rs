@@ -1,17 +1,20 @@
-use anyhow::{anyhow, Context, Ok, Result};
-use std::{collections::HashMap, fs::read_to_string};
+use anyhow::{anyhow, Ok, Result};
+
+use crate::{
+    direction::Dir4,
+    memo::Memo,
+    params::{parse_value, unknown_param, Params, Preset},
+    puzzle::{Answer, Solution},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum NumPadKey {
+pub enum NumPadKey {
     Enter,
     Number(u64),
     Empty,
 }
 
-fn read_input(file_name: &str) -> Result<Vec<Vec<NumPadKey>>> {
-    let input =
-        read_to_string(format!("./src/day_21/{}.txt", file_name)).context("could not read file")?;
-
+fn read_input(input: &str) -> Result<Vec<Vec<NumPadKey>>> {
     input
         .lines()
         .map(|line| {
@@ -30,13 +33,16 @@ fn read_input(file_name: &str) -> Result<Vec<Vec<NumPadKey>>> {
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 enum DirPadKey {
-    Up,
-    Down,
-    Left,
-    Right,
+    Arrow(Dir4),
     Enter,
     Empty,
 }
+
+const UP: DirPadKey = DirPadKey::Arrow(Dir4::North);
+const DOWN: DirPadKey = DirPadKey::Arrow(Dir4::South);
+const LEFT: DirPadKey = DirPadKey::Arrow(Dir4::West);
+const RIGHT: DirPadKey = DirPadKey::Arrow(Dir4::East);
+
 const NUM_PAD: [[NumPadKey; 3]; 4] = [
     [
         NumPadKey::Number(7),
@@ -57,8 +63,8 @@ const NUM_PAD: [[NumPadKey; 3]; 4] = [
 ];
 
 const DIR_PAD: [[DirPadKey; 3]; 2] = [
-    [DirPadKey::Empty, DirPadKey::Up, DirPadKey::Enter],
-    [DirPadKey::Left, DirPadKey::Down, DirPadKey::Right],
+    [DirPadKey::Empty, UP, DirPadKey::Enter],
+    [LEFT, DOWN, RIGHT],
 ];
 
 fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>> {
@@ -90,6 +96,9 @@ fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>>
         end.1 - start.1
     };
 
+    let horizontal_key = DirPadKey::Arrow(if is_left { Dir4::West } else { Dir4::East });
+    let vertical_key = DirPadKey::Arrow(if is_up { Dir4::North } else { Dir4::South });
+
     let mut result = vec![];
 
     // horizontal
@@ -101,11 +110,7 @@ fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>>
             if NUM_PAD[ni][nj] == NumPadKey::Empty {
                 is_horizontal_good = false;
             }
-            horizontal.push(if is_left {
-                DirPadKey::Left
-            } else {
-                DirPadKey::Right
-            });
+            horizontal.push(horizontal_key);
         }
     }
     if dy > 0 {
@@ -114,11 +119,7 @@ fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>>
             if NUM_PAD[ni][nj] == NumPadKey::Empty {
                 is_horizontal_good = false;
             }
-            horizontal.push(if is_up {
-                DirPadKey::Up
-            } else {
-                DirPadKey::Down
-            });
+            horizontal.push(vertical_key);
         }
     }
     // vertical
@@ -130,11 +131,7 @@ fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>>
             if NUM_PAD[ni][nj] == NumPadKey::Empty {
                 is_vertical_good = false;
             }
-            vertical.push(if is_up {
-                DirPadKey::Up
-            } else {
-                DirPadKey::Down
-            });
+            vertical.push(vertical_key);
         }
     }
     if dx > 0 {
@@ -143,11 +140,7 @@ fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>>
             if NUM_PAD[ni][nj] == NumPadKey::Empty {
                 is_vertical_good = false;
             }
-            vertical.push(if is_left {
-                DirPadKey::Left
-            } else {
-                DirPadKey::Right
-            });
+            vertical.push(horizontal_key);
         }
     }
 
@@ -165,81 +158,61 @@ fn shortest_num_pad_paths(from: NumPadKey, to: NumPadKey) -> Vec<Vec<DirPadKey>>
 
 fn shortest_dir_pad_paths(from: DirPadKey, to: DirPadKey) -> Vec<Vec<DirPadKey>> {
     match from {
-        DirPadKey::Up => match to {
-            DirPadKey::Up => vec![vec![DirPadKey::Enter]],
-            DirPadKey::Down => vec![vec![DirPadKey::Down, DirPadKey::Enter]],
-            DirPadKey::Left => vec![vec![DirPadKey::Down, DirPadKey::Left, DirPadKey::Enter]],
-            DirPadKey::Right => vec![
-                vec![DirPadKey::Down, DirPadKey::Right, DirPadKey::Enter],
-                vec![DirPadKey::Right, DirPadKey::Down, DirPadKey::Enter],
+        UP => match to {
+            UP => vec![vec![DirPadKey::Enter]],
+            DOWN => vec![vec![DOWN, DirPadKey::Enter]],
+            LEFT => vec![vec![DOWN, LEFT, DirPadKey::Enter]],
+            RIGHT => vec![
+                vec![DOWN, RIGHT, DirPadKey::Enter],
+                vec![RIGHT, DOWN, DirPadKey::Enter],
             ],
-            DirPadKey::Enter => vec![vec![DirPadKey::Right, DirPadKey::Enter]],
+            DirPadKey::Enter => vec![vec![RIGHT, DirPadKey::Enter]],
             _ => vec![],
         },
-        DirPadKey::Down => match to {
-            DirPadKey::Up => vec![vec![DirPadKey::Up, DirPadKey::Enter]],
-            DirPadKey::Down => vec![vec![DirPadKey::Enter]],
-            DirPadKey::Left => vec![vec![DirPadKey::Left, DirPadKey::Enter]],
-            DirPadKey::Right => vec![vec![DirPadKey::Right, DirPadKey::Enter]],
+        DOWN => match to {
+            UP => vec![vec![UP, DirPadKey::Enter]],
+            DOWN => vec![vec![DirPadKey::Enter]],
+            LEFT => vec![vec![LEFT, DirPadKey::Enter]],
+            RIGHT => vec![vec![RIGHT, DirPadKey::Enter]],
             DirPadKey::Enter => vec![
-                vec![DirPadKey::Up, DirPadKey::Right, DirPadKey::Enter],
-                vec![DirPadKey::Right, DirPadKey::Up, DirPadKey::Enter],
+                vec![UP, RIGHT, DirPadKey::Enter],
+                vec![RIGHT, UP, DirPadKey::Enter],
             ],
             _ => vec![],
         },
-        DirPadKey::Left => match to {
-            DirPadKey::Up => vec![vec![DirPadKey::Right, DirPadKey::Up, DirPadKey::Enter]],
-            DirPadKey::Down => vec![vec![DirPadKey::Right, DirPadKey::Enter]],
-            DirPadKey::Left => vec![vec![DirPadKey::Enter]],
-            DirPadKey::Right => vec![vec![DirPadKey::Right, DirPadKey::Right, DirPadKey::Enter]],
+        LEFT => match to {
+            UP => vec![vec![RIGHT, UP, DirPadKey::Enter]],
+            DOWN => vec![vec![RIGHT, DirPadKey::Enter]],
+            LEFT => vec![vec![DirPadKey::Enter]],
+            RIGHT => vec![vec![RIGHT, RIGHT, DirPadKey::Enter]],
             DirPadKey::Enter => vec![
-                vec![
-                    DirPadKey::Right,
-                    DirPadKey::Up,
-                    DirPadKey::Right,
-                    DirPadKey::Enter,
-                ],
-                vec![
-                    DirPadKey::Right,
-                    DirPadKey::Right,
-                    DirPadKey::Up,
-                    DirPadKey::Enter,
-                ],
+                vec![RIGHT, UP, RIGHT, DirPadKey::Enter],
+                vec![RIGHT, RIGHT, UP, DirPadKey::Enter],
             ],
             _ => vec![],
         },
-        DirPadKey::Right => match to {
-            DirPadKey::Up => vec![
-                vec![DirPadKey::Left, DirPadKey::Up, DirPadKey::Enter],
-                vec![DirPadKey::Up, DirPadKey::Left, DirPadKey::Enter],
+        RIGHT => match to {
+            UP => vec![
+                vec![LEFT, UP, DirPadKey::Enter],
+                vec![UP, LEFT, DirPadKey::Enter],
             ],
-            DirPadKey::Down => vec![vec![DirPadKey::Left, DirPadKey::Enter]],
-            DirPadKey::Left => vec![vec![DirPadKey::Left, DirPadKey::Left, DirPadKey::Enter]],
-            DirPadKey::Right => vec![vec![DirPadKey::Enter]],
-            DirPadKey::Enter => vec![vec![DirPadKey::Up, DirPadKey::Enter]],
+            DOWN => vec![vec![LEFT, DirPadKey::Enter]],
+            LEFT => vec![vec![LEFT, LEFT, DirPadKey::Enter]],
+            RIGHT => vec![vec![DirPadKey::Enter]],
+            DirPadKey::Enter => vec![vec![UP, DirPadKey::Enter]],
             _ => vec![],
         },
         DirPadKey::Enter => match to {
-            DirPadKey::Up => vec![vec![DirPadKey::Left, DirPadKey::Enter]],
-            DirPadKey::Down => vec![
-                vec![DirPadKey::Left, DirPadKey::Down, DirPadKey::Enter],
-                vec![DirPadKey::Down, DirPadKey::Left, DirPadKey::Enter],
+            UP => vec![vec![LEFT, DirPadKey::Enter]],
+            DOWN => vec![
+                vec![LEFT, DOWN, DirPadKey::Enter],
+                vec![DOWN, LEFT, DirPadKey::Enter],
             ],
-            DirPadKey::Left => vec![
-                vec![
-                    DirPadKey::Down,
-                    DirPadKey::Left,
-                    DirPadKey::Left,
-                    DirPadKey::Enter,
-                ],
-                vec![
-                    DirPadKey::Left,
-                    DirPadKey::Down,
-                    DirPadKey::Left,
-                    DirPadKey::Enter,
-                ],
+            LEFT => vec![
+                vec![DOWN, LEFT, LEFT, DirPadKey::Enter],
+                vec![LEFT, DOWN, LEFT, DirPadKey::Enter],
             ],
-            DirPadKey::Right => vec![vec![DirPadKey::Down, DirPadKey::Enter]],
+            RIGHT => vec![vec![DOWN, DirPadKey::Enter]],
             DirPadKey::Enter => vec![vec![DirPadKey::Enter]],
             _ => vec![],
         },
@@ -247,8 +220,8 @@ fn shortest_dir_pad_paths(from: DirPadKey, to: DirPadKey) -> Vec<Vec<DirPadKey>>
     }
 }
 
-fn all_dir_pad_solutions_for_nums(nums: &Vec<NumPadKey>) -> Vec<Vec<Vec<DirPadKey>>> {
-    let nums_from_enter = vec![vec![NumPadKey::Enter], nums.clone()].concat();
+fn all_dir_pad_solutions_for_nums(nums: &[NumPadKey]) -> Vec<Vec<Vec<DirPadKey>>> {
+    let nums_from_enter = [vec![NumPadKey::Enter], nums.to_vec()].concat();
 
     nums_from_enter
         .windows(2)
@@ -257,43 +230,34 @@ fn all_dir_pad_solutions_for_nums(nums: &Vec<NumPadKey>) -> Vec<Vec<Vec<DirPadKe
 }
 
 fn count_paths(
-    dir: &Vec<DirPadKey>,
+    dir: &[DirPadKey],
     level: usize,
-    cache: &mut HashMap<(Vec<DirPadKey>, usize), u64>,
+    cache: &mut Memo<(Vec<DirPadKey>, usize), u64>,
 ) -> u64 {
     if level == 0 {
         return dir.len() as u64;
     }
 
-    if let Some(cached) = cache.get(&(dir.clone(), level)) {
-        return *cached;
-    }
-
-    let dirs_from_enter = vec![vec![DirPadKey::Enter], dir.clone()].concat();
-
-    let computed = dirs_from_enter
-        .windows(2)
-        .map(|window| {
-            shortest_dir_pad_paths(window[0], window[1])
-                .into_iter()
-                .map(|path| count_paths(&path, level - 1, cache))
-                .min()
-                .expect("should have it")
-        })
-        .sum::<u64>();
-
-    cache.insert((dir.clone(), level), computed);
+    cache.get_or_insert_with((dir.to_vec(), level), |cache| {
+        let dirs_from_enter = [vec![DirPadKey::Enter], dir.to_vec()].concat();
 
-    return computed;
+        dirs_from_enter
+            .windows(2)
+            .map(|window| {
+                shortest_dir_pad_paths(window[0], window[1])
+                    .into_iter()
+                    .map(|path| count_paths(&path, level - 1, cache))
+                    .min()
+                    .expect("should have it")
+            })
+            .sum::<u64>()
+    })
 }
 
-pub fn solve() -> Result<()> {
-    let levels_count = 25;
-    let input = read_input("input")?;
+fn sum_complexities(input: &[Vec<NumPadKey>], levels_count: usize) -> u64 {
+    let mut cache = Memo::new();
 
-    let mut cache: HashMap<(Vec<DirPadKey>, usize), u64> = HashMap::new();
-
-    let result: u64 = input
+    input
         .iter()
         .map(|num| {
             let min_for_num = all_dir_pad_solutions_for_nums(num)
@@ -302,7 +266,7 @@ pub fn solve() -> Result<()> {
                     dirs.iter()
                         .map(|dir| count_paths(dir, levels_count, &mut cache))
                         .min()
-                        .unwrap()
+                        .expect("every key has a path")
                 })
                 .sum::<u64>();
 
@@ -313,9 +277,80 @@ pub fn solve() -> Result<()> {
 
             int * min_for_num
         })
-        .sum();
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day21Params {
+    /// directional keypad robots between the person and the numeric keypad
+    pub part_one_levels: usize,
+    pub part_two_levels: usize,
+}
 
-    println!("result: {}", result);
+impl Params for Day21Params {
+    fn preset(_: Preset) -> Self {
+        Day21Params {
+            part_one_levels: 2,
+            part_two_levels: 25,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "part_one_levels" => self.part_one_levels = parse_value(key, value)?,
+            "part_two_levels" => self.part_two_levels = parse_value(key, value)?,
+            _ => return Err(unknown_param(key, &["part_one_levels", "part_two_levels"])),
+        }
+        Ok(())
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Vec<NumPadKey>>;
+    type Params = Day21Params;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_input(input)
+    }
 
-    Ok(())
+    fn part_one(codes: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        Ok(sum_complexities(codes, params.part_one_levels).into())
+    }
+
+    fn part_two(codes: &Self::Input, params: &Self::Params) -> Result<Answer> {
+        Ok(sum_complexities(codes, params.part_two_levels).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+029A
+980A
+179A
+456A
+379A
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day21::part_one(&input, &Day21Params::preset(Preset::Example)).unwrap(),
+            Answer::from(126384)
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day21::part_two(&input, &Day21Params::preset(Preset::Example)).unwrap(),
+            Answer::from(154115708116294_i64)
+        );
+    }
 }
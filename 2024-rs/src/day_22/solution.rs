@@ -1,6 +1,8 @@
-use std::{collections::HashMap, fs::read_to_string};
+use std::collections::HashMap;
 
 use anyhow::{Context, Result};
+
+use crate::puzzle::{Answer, Solution};
 use itertools::Itertools;
 
 fn next_secret(mut secret: i64) -> i64 {
@@ -27,74 +29,115 @@ fn next_secret(mut secret: i64) -> i64 {
     secret
 }
 
-fn read_secrets(file_name: &str) -> Result<Vec<i64>> {
-    let input =
-        read_to_string(format!("./src/day_22/{}.txt", file_name)).context("could not read file")?;
+fn read_secrets(input: &str) -> Result<Vec<i64>> {
     input
         .lines()
         .map(|line| line.parse::<i64>().context("could not parse line"))
         .collect()
 }
 
-fn part_one() -> Result<()> {
-    let secrets = read_secrets("input")?;
-
-    let result = secrets
-        .iter()
-        .map(|&secret| (0..2000).fold(secret, |acc, _| next_secret(acc)))
-        .sum::<i64>();
+pub struct Day22;
 
-    println!("part one result: {}", result);
+impl Solution for Day22 {
+    type Input = Vec<i64>;
+    type Params = ();
 
-    Ok(())
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_secrets(input)
+    }
 
-fn part_two() -> Result<()> {
-    let secrets = read_secrets("input")?;
+    fn part_one(secrets: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let result = secrets
+            .iter()
+            .map(|&secret| (0..2000).fold(secret, |acc, _| next_secret(acc)))
+            .sum::<i64>();
 
-    let mut cache_per_secret: Vec<HashMap<_, i64>> = vec![];
+        Ok(result.into())
+    }
 
-    secrets.iter().enumerate().for_each(|(secret_i, &secret)| {
-        cache_per_secret.push(HashMap::new());
+    fn part_two(secrets: &Self::Input, _: &Self::Params) -> Result<Answer> {
+        let mut cache_per_secret: Vec<HashMap<_, i64>> = vec![];
 
-        let mut seq = vec![];
-        let mut secret_inner = secret;
+        secrets.iter().enumerate().for_each(|(secret_i, &secret)| {
+            cache_per_secret.push(HashMap::new());
 
-        (0..2000).into_iter().for_each(|_| {
-            seq.push(secret_inner % 10);
-            secret_inner = next_secret(secret_inner);
-        });
+            let mut seq = vec![];
+            let mut secret_inner = secret;
 
-        seq.windows(2)
-            .map(|window| window[0] - window[1])
-            .tuple_windows::<(_, _, _, _)>()
-            .enumerate()
-            .for_each(|(i, key)| {
-                if cache_per_secret[secret_i].contains_key(&key) {
-                    return;
-                }
-                let val = seq[i + 4];
-                cache_per_secret[secret_i].insert(key, val);
+            (0..2000).for_each(|_| {
+                seq.push(secret_inner % 10);
+                secret_inner = next_secret(secret_inner);
             });
-    });
 
-    let mut results_per_quarter = HashMap::new();
+            seq.windows(2)
+                .map(|window| window[0] - window[1])
+                .tuple_windows::<(_, _, _, _)>()
+                .enumerate()
+                .for_each(|(i, key)| {
+                    if cache_per_secret[secret_i].contains_key(&key) {
+                        return;
+                    }
+                    let val = seq[i + 4];
+                    cache_per_secret[secret_i].insert(key, val);
+                });
+        });
+
+        let mut results_per_quarter = HashMap::new();
 
-    cache_per_secret.iter().for_each(|cache| {
-        cache.iter().for_each(|(&key, &val)| {
-            *results_per_quarter.entry(key).or_insert(0) += val;
+        cache_per_secret.iter().for_each(|cache| {
+            cache.iter().for_each(|(&key, &val)| {
+                *results_per_quarter.entry(key).or_insert(0) += val;
+            });
         });
-    });
 
-    println!(
-        "part two result: {}",
-        results_per_quarter.into_values().max().unwrap()
-    );
+        let best = results_per_quarter
+            .into_values()
+            .max()
+            .context("no buyers")?;
 
-    Ok(())
+        Ok(best.into())
+    }
 }
 
-pub fn solve() -> Result<()> {
-    part_one()?;
-    part_two()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1
+10
+100
+2024
+";
+
+    const EXAMPLE_PART_TWO: &str = "\
+1
+2
+3
+2024
+";
+
+    #[test]
+    fn part_one_example() {
+        let input = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Day22::part_one(&input, &()).unwrap(),
+            Answer::from(37327623)
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day22::parse(EXAMPLE_PART_TWO).unwrap();
+        assert_eq!(Day22::part_two(&input, &()).unwrap(), Answer::from(23));
+    }
+
+    #[test]
+    fn part_two_needs_buyers() {
+        let input = Day22::parse("").unwrap();
+        assert_eq!(
+            Day22::part_two(&input, &()).unwrap_err().to_string(),
+            "no buyers"
+        );
+    }
 }
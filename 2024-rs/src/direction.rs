@@ -0,0 +1,263 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::point::Point;
+
+/// One of the four straight directions, with north pointing up the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    /// Clockwise, starting from north.
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    pub fn turn_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Dir4::East | Dir4::West)
+    }
+
+    /// Unit step in `x,y` coordinates, `y` growing downwards.
+    pub const fn delta(self) -> Point {
+        match self {
+            Dir4::North => Point::new(0, -1),
+            Dir4::East => Point::new(1, 0),
+            Dir4::South => Point::new(0, 1),
+            Dir4::West => Point::new(-1, 0),
+        }
+    }
+
+    /// Unit step as a `(row, column)` offset for [`crate::grid::Grid`].
+    pub const fn offset(self) -> (isize, isize) {
+        Dir8::from_dir4(self).offset()
+    }
+
+    /// Parses one of `^>v<`.
+    pub fn from_arrow(c: char) -> Option<Dir4> {
+        match c {
+            '^' => Some(Dir4::North),
+            '>' => Some(Dir4::East),
+            'v' => Some(Dir4::South),
+            '<' => Some(Dir4::West),
+            _ => None,
+        }
+    }
+
+    /// Parses one of `NESW`.
+    pub fn from_compass(c: char) -> Option<Dir4> {
+        match c {
+            'N' => Some(Dir4::North),
+            'E' => Some(Dir4::East),
+            'S' => Some(Dir4::South),
+            'W' => Some(Dir4::West),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(self) -> char {
+        match self {
+            Dir4::North => '^',
+            Dir4::East => '>',
+            Dir4::South => 'v',
+            Dir4::West => '<',
+        }
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = anyhow::Error;
+
+    /// Accepts both arrows and compass letters.
+    fn try_from(c: char) -> Result<Self> {
+        Dir4::from_arrow(c)
+            .or_else(|| Dir4::from_compass(c))
+            .ok_or_else(|| anyhow!("unknown direction '{}'", c))
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir4::try_from(c),
+            _ => Err(anyhow!("unknown direction '{}'", s)),
+        }
+    }
+}
+
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_arrow())
+    }
+}
+
+/// One of the four straight and four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// Clockwise, starting from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub const fn from_dir4(dir: Dir4) -> Dir8 {
+        match dir {
+            Dir4::North => Dir8::North,
+            Dir4::East => Dir8::East,
+            Dir4::South => Dir8::South,
+            Dir4::West => Dir8::West,
+        }
+    }
+
+    /// Turns by 45 degrees.
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns by 45 degrees.
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Unit step in `x,y` coordinates, `y` growing downwards.
+    pub const fn delta(self) -> Point {
+        let (d_row, d_col) = self.offset();
+        Point::new(d_col as i64, d_row as i64)
+    }
+
+    /// Unit step as a `(row, column)` offset for [`crate::grid::Grid`].
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Dir8::North => (-1, 0),
+            Dir8::NorthEast => (-1, 1),
+            Dir8::East => (0, 1),
+            Dir8::SouthEast => (1, 1),
+            Dir8::South => (1, 0),
+            Dir8::SouthWest => (1, -1),
+            Dir8::West => (0, -1),
+            Dir8::NorthWest => (-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::from_dir4(dir)
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = anyhow::Error;
+
+    /// Parses compass points such as `N` or `SW`, single arrows are accepted too.
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "NE" => Ok(Dir8::NorthEast),
+            "SE" => Ok(Dir8::SouthEast),
+            "SW" => Ok(Dir8::SouthWest),
+            "NW" => Ok(Dir8::NorthWest),
+            _ => s.parse::<Dir4>().map(Dir8::from),
+        }
+    }
+}
+
+impl Display for Dir8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Dir8::North => "N",
+            Dir8::NorthEast => "NE",
+            Dir8::East => "E",
+            Dir8::SouthEast => "SE",
+            Dir8::South => "S",
+            Dir8::SouthWest => "SW",
+            Dir8::West => "W",
+            Dir8::NorthWest => "NW",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_goes_around() {
+        assert_eq!(Dir4::North.turn_right(), Dir4::East);
+        assert_eq!(Dir4::North.turn_left(), Dir4::West);
+        assert_eq!(Dir4::East.reverse(), Dir4::West);
+        assert_eq!(Dir8::North.turn_left(), Dir8::NorthWest);
+        assert_eq!(Dir8::SouthWest.reverse(), Dir8::NorthEast);
+        assert!(Dir4::ALL
+            .iter()
+            .all(|&dir| dir.turn_right().turn_left() == dir));
+    }
+
+    #[test]
+    fn deltas_match_offsets() {
+        assert_eq!(Dir4::North.delta(), Point::new(0, -1));
+        assert_eq!(Dir4::West.offset(), (0, -1));
+        assert_eq!(Dir8::SouthEast.delta(), Point::new(1, 1));
+        assert!(Dir4::ALL
+            .iter()
+            .all(|&dir| dir.delta() + dir.reverse().delta() == Point::new(0, 0)));
+    }
+
+    #[test]
+    fn parse_arrows_and_compass() {
+        let arrows: Vec<Dir4> = "^>v<".chars().map(|c| c.try_into().unwrap()).collect();
+        let compass: Vec<Dir4> = "NESW".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(arrows, Dir4::ALL);
+        assert_eq!(compass, Dir4::ALL);
+        assert_eq!("SW".parse::<Dir8>().unwrap(), Dir8::SouthWest);
+        assert_eq!("v".parse::<Dir8>().unwrap(), Dir8::South);
+        assert!(Dir4::try_from('x').is_err());
+        assert!("NN".parse::<Dir4>().is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+};
+
+/// Ordering rules, each saying one node has to come before another.
+#[derive(Debug, Clone)]
+pub struct Rules<N> {
+    successors: HashMap<N, HashSet<N>>,
+}
+
+/// Nodes that have to come before each other in a loop, the first one follows the last.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Display> Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rules form a cycle: ")?;
+        for node in &self.0 {
+            write!(f, "{} -> ", node)?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: Debug + Display> Error for Cycle<N> {}
+
+/// Rule `before -> after` broken by a sequence, with the positions of both nodes in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation<N> {
+    pub before: N,
+    pub after: N,
+    pub before_position: usize,
+    pub after_position: usize,
+}
+
+impl<N: Display> Display for Violation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule {} -> {} broken: {} at position {} comes after {} at position {}",
+            self.before,
+            self.after,
+            self.before,
+            self.before_position,
+            self.after,
+            self.after_position
+        )
+    }
+}
+
+impl<N: Debug + Display> Error for Violation<N> {}
+
+impl<N: Copy + Eq + Hash> Rules<N> {
+    pub fn new() -> Self {
+        Rules {
+            successors: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, before: N, after: N) {
+        self.successors.entry(before).or_default().insert(after);
+    }
+
+    /// Whether a rule directly says `before` comes before `after`.
+    pub fn requires(&self, before: N, after: N) -> bool {
+        self.successors
+            .get(&before)
+            .is_some_and(|successors| successors.contains(&after))
+    }
+
+    /// The first rule `sequence` breaks, scanning it from the front.
+    pub fn check(&self, sequence: &[N]) -> Result<(), Violation<N>> {
+        for (before_position, &before) in sequence.iter().enumerate() {
+            if let Some(after_position) = sequence[..before_position]
+                .iter()
+                .position(|&earlier| self.requires(before, earlier))
+            {
+                return Err(Violation {
+                    before,
+                    after: sequence[after_position],
+                    before_position,
+                    after_position,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Orders `nodes` so that every rule between two of them holds, rules mentioning other nodes
+    /// are ignored. Nodes no rule orders keep their relative order, repeated nodes are kept once.
+    pub fn sort(&self, nodes: &[N]) -> Result<Vec<N>, Cycle<N>> {
+        let mut index: HashMap<N, usize> = HashMap::new();
+        let mut unique: Vec<N> = vec![];
+        for &node in nodes {
+            index.entry(node).or_insert_with(|| {
+                unique.push(node);
+                unique.len() - 1
+            });
+        }
+
+        let edges: Vec<Vec<usize>> = unique
+            .iter()
+            .map(|node| {
+                self.successors.get(node).map_or(vec![], |successors| {
+                    successors
+                        .iter()
+                        .filter_map(|successor| index.get(successor).copied())
+                        .collect()
+                })
+            })
+            .collect();
+
+        let mut incoming = vec![0; unique.len()];
+        edges.iter().flatten().for_each(|&to| incoming[to] += 1);
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..unique.len())
+            .filter(|&i| incoming[i] == 0)
+            .map(Reverse)
+            .collect();
+        let mut sorted = Vec::with_capacity(unique.len());
+
+        while let Some(Reverse(i)) = ready.pop() {
+            sorted.push(unique[i]);
+            for &to in &edges[i] {
+                incoming[to] -= 1;
+                if incoming[to] == 0 {
+                    ready.push(Reverse(to));
+                }
+            }
+        }
+
+        if sorted.len() == unique.len() {
+            return Ok(sorted);
+        }
+
+        // every node left over still has an incoming edge from another left over node, so walking
+        // those edges backwards has to run into a node it has already seen
+        let leftover = |i: usize| incoming[i] > 0;
+        let mut path: Vec<usize> = vec![(0..unique.len())
+            .find(|&i| leftover(i))
+            .expect("a node is left over")];
+        let mut seen_at: HashMap<usize, usize> = HashMap::from([(path[0], 0)]);
+
+        loop {
+            let current = *path.last().expect("path is never empty");
+            let previous = (0..unique.len())
+                .find(|&from| leftover(from) && edges[from].contains(&current))
+                .expect("left over nodes have a left over predecessor");
+            if let Some(&start) = seen_at.get(&previous) {
+                let mut cycle: Vec<N> = path[start..].iter().map(|&i| unique[i]).collect();
+                cycle.reverse();
+                return Err(Cycle(cycle));
+            }
+            seen_at.insert(previous, path.len());
+            path.push(previous);
+        }
+    }
+}
+
+impl<N: Copy + Eq + Hash> Default for Rules<N> {
+    fn default() -> Self {
+        Rules::new()
+    }
+}
+
+impl<N: Copy + Eq + Hash> FromIterator<(N, N)> for Rules<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(rules: I) -> Self {
+        let mut result = Rules::new();
+        rules
+            .into_iter()
+            .for_each(|(before, after)| result.add(before, after));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_only_the_given_nodes() {
+        let rules: Rules<i32> = [(1, 2), (2, 3), (4, 1), (9, 4)].into_iter().collect();
+
+        assert_eq!(rules.sort(&[3, 1, 2]), Ok(vec![1, 2, 3]));
+        // 2 is not among the nodes, so nothing puts 3 after 1 and the input order is kept
+        assert_eq!(rules.sort(&[5, 3, 1, 4, 4]), Ok(vec![5, 3, 4, 1]));
+        assert_eq!(rules.sort(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn reports_the_cycle() {
+        let rules: Rules<i32> = [(1, 2), (2, 3), (3, 1), (0, 1)].into_iter().collect();
+
+        let cycle = rules.sort(&[0, 1, 2, 3]).unwrap_err();
+        let mut nodes = cycle.0.clone();
+        nodes.sort();
+        assert_eq!(nodes, vec![1, 2, 3]);
+        for (i, &node) in cycle.0.iter().enumerate() {
+            assert!(rules.requires(node, cycle.0[(i + 1) % cycle.0.len()]));
+        }
+
+        assert!(rules.sort(&[0, 1, 2]).is_ok());
+        assert_eq!(
+            Rules::from_iter([(7, 7)])
+                .sort(&[7])
+                .unwrap_err()
+                .to_string(),
+            "rules form a cycle: 7 -> 7"
+        );
+    }
+
+    #[test]
+    fn names_the_first_broken_rule() {
+        let rules: Rules<i32> = [(1, 2), (2, 3), (1, 3)].into_iter().collect();
+
+        assert_eq!(rules.check(&[1, 2, 3]), Ok(()));
+        let violation = rules.check(&[3, 2, 1]).unwrap_err();
+        assert_eq!(
+            violation,
+            Violation {
+                before: 2,
+                after: 3,
+                before_position: 1,
+                after_position: 0
+            }
+        );
+        assert_eq!(
+            violation.to_string(),
+            "rule 2 -> 3 broken: 2 at position 1 comes after 3 at position 0"
+        );
+    }
+}
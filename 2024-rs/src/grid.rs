@@ -0,0 +1,280 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    cell::CharCell,
+    direction::{Dir4, Dir8},
+};
+
+/// Cell position as `(row, column)`, see [`crate::point::Point`] for the `x,y` form.
+pub type Pos = (usize, usize);
+
+/// Rectangular grid stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        if let Some(row) = rows.iter().position(|row| row.len() != width) {
+            return Err(anyhow!(
+                "row {} has {} cells, expected {}",
+                row + 1,
+                rows[row].len(),
+                width
+            ));
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses one row per line, mapping every character to a cell, errors name the line and column.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        Ok(crate::parse::grid(input, cell)?)
+    }
+
+    /// Parses a grid of [`CharCell`] values.
+    pub fn parse_cells(input: &str) -> Result<Self>
+    where
+        T: CharCell,
+    {
+        Grid::parse(input, T::parse_char)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        match self.contains(pos) {
+            true => Some(&self.cells[pos.0 * self.width + pos.1]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => Some(&mut self.cells[pos.0 * self.width + pos.1]),
+            false => None,
+        }
+    }
+
+    /// Moves `pos` by `delta`, `None` when that leaves the grid.
+    pub fn offset(&self, (row, col): Pos, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        let next = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    /// Moves `pos` one cell towards `dir`, `None` when that leaves the grid.
+    pub fn step(&self, pos: Pos, dir: impl Into<Dir8>) -> Option<Pos> {
+        self.offset(pos, dir.into().offset())
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Positions starting at `start` and stepping by `delta` until the edge.
+    pub fn ray(&self, start: Pos, delta: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        let start = self.contains(start).then_some(start);
+        std::iter::successors(start, move |&pos| self.offset(pos, delta))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    /// Diagonals going down and right, starting from the left and top edges.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<Pos>> + '_ {
+        let starts = (0..self.height)
+            .rev()
+            .map(|row| (row, 0))
+            .chain((1..self.width).map(|col| (0, col)));
+        starts.map(|start| self.ray(start, (1, 1)).collect())
+    }
+
+    /// Diagonals going down and left, starting from the top and right edges.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<Pos>> + '_ {
+        let last_col = self.width.saturating_sub(1);
+        let starts = (0..self.width)
+            .map(|col| (0, col))
+            .chain((1..self.height).map(move |row| (row, last_col)));
+        starts.map(|start| self.ray(start, (1, -1)).collect())
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn position_of(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid with one character per cell.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of the {}x{} grid",
+                pos, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!(
+                "position {:?} is outside of the {}x{} grid",
+                pos, width, height
+            )
+        })
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            row.iter().try_for_each(|cell| write!(f, "{}", cell))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef", Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.position_of(&'e'), Some((1, 1)));
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert!(Grid::parse("ab\nc", Ok).is_err());
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = sample();
+        let corner: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.step((1, 1), Dir4::North), Some((0, 1)));
+        assert_eq!(grid.step((1, 1), Dir8::SouthEast), None);
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+    }
+
+    #[test]
+    fn lines_through_the_grid() {
+        let grid = sample();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        let diagonals: Vec<String> = grid
+            .diagonals()
+            .map(|line| line.into_iter().map(|pos| grid[pos]).collect())
+            .collect();
+        assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+        let anti_diagonals: Vec<String> = grid
+            .anti_diagonals()
+            .map(|line| line.into_iter().map(|pos| grid[pos]).collect())
+            .collect();
+        assert_eq!(anti_diagonals, vec!["a", "bd", "ce", "f"]);
+        assert_eq!(grid.ray((0, 0), (0, 1)).count(), 3);
+    }
+}
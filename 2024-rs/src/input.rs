@@ -0,0 +1,114 @@
+use std::{
+    env,
+    fmt::Display,
+    fs::read_to_string,
+    io::{stdin, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+/// Environment variable holding the input path, `{day}` is replaced by the zero padded day.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+/// Environment variable holding the directory with `day_XX/input.txt` files.
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+/// Path value that stands for standard input.
+pub const STDIN_PATH: &str = "-";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    Stdin,
+    /// a file along with the setting it was taken from
+    File {
+        path: PathBuf,
+        origin: &'static str,
+    },
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Stdin => write!(f, "stdin"),
+            Location::File { path, origin } => write!(f, "'{}' (from {})", path.display(), origin),
+        }
+    }
+}
+
+/// Resolves puzzle inputs independently of the current directory.
+///
+/// The first setting present wins: the explicit input path, then `AOC_INPUT`,
+/// then `day_XX/input.txt` inside the data directory (`--data-dir`, `AOC_DATA_DIR`,
+/// or the crate's `src` directory).
+#[derive(Debug, Clone, Default)]
+pub struct InputProvider {
+    input: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+}
+
+fn day_path(path: &str, day: u8) -> PathBuf {
+    PathBuf::from(path.replace("{day}", &format!("{:02}", day)))
+}
+
+impl InputProvider {
+    pub fn new(input: Option<PathBuf>, data_dir: Option<PathBuf>) -> Self {
+        InputProvider { input, data_dir }
+    }
+
+    pub fn locate(&self, day: u8) -> Location {
+        let explicit = match &self.input {
+            Some(path) => Some((path.to_string_lossy().to_string(), "--input")),
+            None => env::var(INPUT_ENV).ok().map(|path| (path, INPUT_ENV)),
+        };
+
+        if let Some((path, origin)) = explicit {
+            if path == STDIN_PATH {
+                return Location::Stdin;
+            }
+            return Location::File {
+                path: day_path(&path, day),
+                origin,
+            };
+        }
+
+        let (data_dir, origin) = self.data_dir();
+        Location::File {
+            path: data_dir.join(format!("day_{:02}", day)).join("input.txt"),
+            origin,
+        }
+    }
+
+    /// Whether every day reads standard input, which can only be consumed once.
+    pub fn reads_stdin(&self) -> bool {
+        self.locate(0) == Location::Stdin
+    }
+
+    /// Directory holding the inputs along with the setting it was taken from.
+    pub fn data_dir(&self) -> (PathBuf, &'static str) {
+        match &self.data_dir {
+            Some(dir) => (dir.clone(), "--data-dir"),
+            None => match env::var(DATA_DIR_ENV) {
+                Ok(dir) => (PathBuf::from(dir), DATA_DIR_ENV),
+                Err(_) => (
+                    Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+                    "default data directory",
+                ),
+            },
+        }
+    }
+
+    pub fn load(&self, day: u8) -> Result<String> {
+        let location = self.locate(day);
+        let contents = match &location {
+            Location::Stdin => {
+                let mut contents = String::new();
+                stdin().read_to_string(&mut contents).map(|_| contents)
+            }
+            Location::File { path, .. } => read_to_string(path),
+        };
+
+        contents.context(format!(
+            "could not load input for day {}, tried {}",
+            day, location
+        ))
+    }
+}
@@ -1,3 +1,6 @@
+pub mod answers;
+pub mod bench;
+pub mod cell;
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
@@ -20,3 +23,19 @@ pub mod day_19;
 pub mod day_20;
 pub mod day_21;
 pub mod day_22;
+pub mod direction;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod math;
+pub mod memo;
+pub mod params;
+pub mod parse;
+pub mod point;
+pub mod puzzle;
+pub mod region;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod search;
+pub mod union_find;
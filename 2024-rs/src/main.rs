@@ -1,6 +1,433 @@
-use anyhow::Result;
-use aoc_2024_rs::day_22::solution;
+use std::{panic, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use aoc_2024_rs::{
+    answers::{KnownAnswers, Status, ANSWERS_ENV, ANSWERS_FILE},
+    bench::{
+        baseline_path, bench_day, compare, default_baseline_dir, BenchReport, ComparisonTable,
+        Verdict,
+    },
+    input::InputProvider,
+    params::{parse_override, ParamConfig, ParamSettings, Preset},
+    puzzle::Answer,
+    registry::{self, Day},
+    report::{to_csv, to_json, Format},
+    runner::{run_parallel, Job, PartRecord, SummaryTable},
+};
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Path to the puzzle input, `-` reads stdin, falls back to $AOC_INPUT
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Directory with day_XX/input.txt files, falls back to $AOC_DATA_DIR, then the crate's src
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+}
+
+impl InputArgs {
+    fn provider(self) -> InputProvider {
+        InputProvider::new(self.input, self.data_dir)
+    }
+}
+
+#[derive(Debug, Args)]
+struct ParamArgs {
+    /// Parameter preset, `example` or `real`
+    #[arg(long)]
+    preset: Option<Preset>,
+    /// Override a single parameter, e.g. `--param width=11`
+    #[arg(long = "param", value_parser = parse_override)]
+    params: Vec<(String, String)>,
+    /// TOML file with per-day parameters in [day_XX] sections
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+impl ParamArgs {
+    /// Settings for a day, the command line takes precedence over the config file.
+    fn settings(&self, day: u8) -> Result<ParamSettings> {
+        let config = match &self.config {
+            Some(path) => ParamConfig::load(path)?,
+            None => ParamConfig::default(),
+        };
+        let cli = ParamSettings {
+            preset: self.preset,
+            overrides: self.params.clone(),
+        };
+        Ok(config.day(day).merge(&cli))
+    }
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// Repeat the run N times and report timing statistics
+    #[arg(long, value_name = "N")]
+    bench: Option<usize>,
+    /// Write the benchmark statistics as JSON to this path
+    #[arg(long, requires = "bench")]
+    json: Option<PathBuf>,
+    /// Store the benchmark as a named baseline, merged into an existing one
+    #[arg(long, value_name = "NAME", requires = "bench")]
+    save_baseline: Option<String>,
+    /// Compare the benchmark against a named baseline
+    #[arg(long, value_name = "NAME", requires = "bench")]
+    baseline: Option<String>,
+    /// Slowdown of the median in percent that is flagged as a regression
+    #[arg(long, default_value_t = 10.0, requires = "baseline")]
+    threshold: f64,
+    /// Directory with the baselines, defaults to target/baselines in the crate
+    #[arg(long)]
+    baseline_dir: Option<PathBuf>,
+}
+
+impl BenchArgs {
+    fn baseline_path(&self, name: &str) -> PathBuf {
+        let dir = self
+            .baseline_dir
+            .clone()
+            .unwrap_or_else(default_baseline_dir);
+        baseline_path(&dir, name)
+    }
+
+    fn report(&self, report: BenchReport) -> Result<()> {
+        print!("{}", report);
+
+        if let Some(path) = &self.json {
+            report.save(path)?;
+        }
+
+        if let Some(name) = &self.save_baseline {
+            let path = self.baseline_path(name);
+            let mut baseline = match path.exists() {
+                true => BenchReport::load(&path)?,
+                false => BenchReport::default(),
+            };
+            baseline.merge(report.clone());
+            baseline.save(&path)?;
+            println!("saved baseline '{}' to '{}'", name, path.display());
+        }
+
+        if let Some(name) = &self.baseline {
+            let baseline = BenchReport::load(&self.baseline_path(name))?;
+            let comparisons = compare(&baseline, &report, self.threshold);
+            print!("{}", ComparisonTable(&comparisons));
+
+            let slower = comparisons
+                .iter()
+                .filter(|comparison| comparison.verdict == Verdict::Slower)
+                .count();
+            if slower > 0 {
+                return Err(anyhow!(
+                    "{} stages slowed down by more than {}% against baseline '{}'",
+                    slower,
+                    self.threshold,
+                    name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the solver for a given day
+    #[command(group = clap::ArgGroup::new("days").required(true))]
+    Run {
+        /// Day to run, e.g. 17
+        #[arg(long, group = "days")]
+        day: Option<u8>,
+        /// Run every day in parallel and print a summary table
+        #[arg(long, group = "days", conflicts_with_all = ["part", "bench"])]
+        all: bool,
+        /// Worker threads for --all, defaults to the available parallelism
+        #[arg(long, requires = "all")]
+        jobs: Option<usize>,
+        /// Part to run, both parts are run when omitted
+        #[arg(long)]
+        part: Option<u8>,
+        /// Output format of the answers: text, json or csv
+        #[arg(long, default_value_t = Format::Text)]
+        format: Format,
+        #[command(flatten)]
+        bench: BenchArgs,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+    /// Rerun the solvers and compare against the accepted answers
+    Verify {
+        /// Day to verify, every day is verified when omitted
+        #[arg(long)]
+        day: Option<u8>,
+        /// Answers file, falls back to $AOC_ANSWERS, then answers.toml in the data directory
+        #[arg(long)]
+        answers: Option<PathBuf>,
+        /// Store the actual value of every MISSING part as its accepted answer
+        #[arg(long)]
+        record: bool,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+}
+
+/// Prints the records in the machine readable formats, text output is up to the caller.
+fn print_records(records: &[PartRecord], format: Format) -> Result<()> {
+    match format {
+        Format::Json => println!("{}", to_json(records)?),
+        Format::Csv => print!("{}", to_csv(records)),
+        Format::Text => {}
+    }
+
+    let failed = records
+        .iter()
+        .filter(|record| record.error().is_some())
+        .count();
+    match failed {
+        0 => Ok(()),
+        failed => Err(anyhow!("{} parts failed", failed)),
+    }
+}
+
+fn run_all(
+    jobs_count: Option<usize>,
+    format: Format,
+    provider: InputProvider,
+    params: ParamArgs,
+) -> Result<()> {
+    if provider.reads_stdin() {
+        bail!(
+            "--all needs an input per day, use a path with {{day}} or --data-dir instead of stdin"
+        );
+    }
+
+    let jobs = registry::DAYS
+        .iter()
+        .map(|day| {
+            Ok(Job {
+                day,
+                input: provider.load(day.number),
+                settings: params.settings(day.number)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // panics are reported in the table, keep them from cluttering stderr
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let records = run_parallel(&jobs, jobs_count);
+    panic::set_hook(hook);
+
+    if format == Format::Text {
+        let failed = records
+            .iter()
+            .filter(|record| record.error().is_some())
+            .count();
+        print!("{}", SummaryTable(&records));
+        println!("{} solved, {} failed", records.len() - failed, failed);
+    }
+
+    print_records(&records, format)
+}
+
+fn run(
+    day: u8,
+    part: Option<u8>,
+    format: Format,
+    bench: BenchArgs,
+    provider: InputProvider,
+    params: ParamArgs,
+) -> Result<()> {
+    let day = registry::find_day(day)?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Day::PARTS.to_vec(),
+    };
+    parts.iter().try_for_each(|&part| day.check_part(part))?;
+
+    let settings = params.settings(day.number)?;
+    let input = provider.load(day.number);
+
+    if let Some(runs) = bench.bench {
+        return bench.report(BenchReport {
+            entries: bench_day(day, &input?, &settings, &parts, runs)?,
+        });
+    }
+
+    // load and parse failures become records, so every format reports them
+    let (parse, records): (Option<Duration>, Vec<PartRecord>) =
+        match input.and_then(|input| day.run(&input, &settings, &parts)) {
+            Ok(run) => (
+                Some(run.parse),
+                run.parts
+                    .into_iter()
+                    .map(|part| PartRecord::from_timed(day.number, part))
+                    .collect(),
+            ),
+            Err(error) => (
+                None,
+                parts
+                    .iter()
+                    .map(|&part| PartRecord::failed(day.number, part, &error))
+                    .collect(),
+            ),
+        };
+
+    if format == Format::Text {
+        if let Some(parse) = parse {
+            println!("day {} parse: {:.2?}", day.number, parse);
+        }
+        records
+            .iter()
+            .for_each(|record| match (record.answer(), record.error()) {
+                (Some(answer), _) => println!(
+                    "day {} part {}: {} ({:.2?})",
+                    record.day,
+                    record.part,
+                    answer,
+                    record.elapsed.unwrap_or_default()
+                ),
+                (None, error) => println!(
+                    "day {} part {}: error: {}",
+                    record.day,
+                    record.part,
+                    error.unwrap_or_default()
+                ),
+            });
+    }
+
+    print_records(&records, format)
+}
+
+fn verify(
+    day: Option<u8>,
+    answers_path: Option<PathBuf>,
+    record: bool,
+    provider: InputProvider,
+    params: ParamArgs,
+) -> Result<()> {
+    let days = match day {
+        Some(day) => vec![registry::find_day(day)?],
+        None if provider.reads_stdin() => {
+            bail!("verifying every day needs an input per day, pass --day to read stdin")
+        }
+        None => registry::DAYS.iter().collect(),
+    };
+    let answers_path = answers_path
+        .or_else(|| std::env::var(ANSWERS_ENV).ok().map(PathBuf::from))
+        .unwrap_or_else(|| provider.data_dir().0.join(ANSWERS_FILE));
+    let mut answers = KnownAnswers::load(&answers_path)?;
+
+    let mut statuses = vec![];
+    for day in days {
+        let settings = params.settings(day.number)?;
+        let actuals: Vec<(u8, Result<Answer>)> = match provider
+            .load(day.number)
+            .and_then(|input| day.run(&input, &settings, &Day::PARTS))
+        {
+            Ok(run) => run
+                .parts
+                .into_iter()
+                .map(|part| (part.part, part.answer))
+                .collect(),
+            Err(error) => Day::PARTS
+                .iter()
+                .map(|&part| (part, Err(anyhow!("{:#}", error))))
+                .collect(),
+        };
+
+        for (part, actual) in actuals {
+            let expected = answers.get(day.number, part);
+            let status = Status::of(expected, &actual);
+
+            let actual_text = match &actual {
+                Ok(answer) => answer.to_string(),
+                Err(error) => format!("error: {:#}", error),
+            };
+            match expected {
+                Some(expected) => println!(
+                    "day {:02} part {}: {} (expected {}, actual {})",
+                    day.number, part, status, expected, actual_text
+                ),
+                None => println!(
+                    "day {:02} part {}: {} (actual {})",
+                    day.number, part, status, actual_text
+                ),
+            }
+
+            if let (true, Status::Missing, Ok(answer)) = (record, status, &actual) {
+                answers.insert(day.number, part, answer);
+            }
+            statuses.push(status);
+        }
+    }
+
+    if record {
+        answers.save(&answers_path)?;
+        println!("recorded answers in '{}'", answers_path.display());
+    }
+
+    let count = |status: Status| statuses.iter().filter(|&&s| s == status).count();
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    );
+
+    match count(Status::Fail) {
+        0 => Ok(()),
+        failed => Err(anyhow!("{} parts failed verification", failed)),
+    }
+}
 
 fn main() -> Result<()> {
-    solution::solve()
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            all: true,
+            jobs,
+            format,
+            input,
+            params,
+            ..
+        } => run_all(jobs, format, input.provider(), params),
+        Command::Run {
+            day,
+            part,
+            format,
+            bench,
+            input,
+            params,
+            ..
+        } => run(
+            day.expect("either --day or --all is required"),
+            part,
+            format,
+            bench,
+            input.provider(),
+            params,
+        ),
+        Command::Verify {
+            day,
+            answers,
+            record,
+            input,
+            params,
+        } => verify(day, answers, record, input.provider(), params),
+    }
 }
@@ -0,0 +1,258 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Returns `(g, x, y)` with `a * x + b * y == g`, where `g` is the non-negative gcd.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+pub fn gcd(a: i128, b: i128) -> i128 {
+    extended_gcd(a, b).0
+}
+
+/// Non-negative, 0 if either side is 0.
+pub fn lcm(a: i128, b: i128) -> i128 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// Least common multiple of every value, 1 for none.
+pub fn lcm_all(values: impl IntoIterator<Item = i128>) -> i128 {
+    values.into_iter().fold(1, lcm)
+}
+
+/// `x` in `0..modulus` with `a * x ≡ 1`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a, modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Smallest non-negative `x` with `x ≡ residue (mod modulus)` for every pair, together with the
+/// lcm of the moduli, which does not need to be pairwise coprime.
+/// `None` if the congruences contradict each other.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            let (g, p, _) = extended_gcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+            let step = modulus / g;
+            let t = (diff / g % step * p).rem_euclid(step);
+            let combined = m * step;
+            Some(((x + m * t).rem_euclid(combined), combined))
+        })
+}
+
+/// Exact fraction, always reduced with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Panics on a zero denominator.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational { num: value, den: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics when dividing by zero.
+    fn div(self, rhs: Rational) -> Rational {
+        Rational::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Solves `matrix * x = rhs` exactly with Gaussian elimination over fractions.
+/// `None` unless the system has exactly one solution.
+pub fn solve_linear<const N: usize>(
+    matrix: [[i128; N]; N],
+    rhs: [i128; N],
+) -> Option<[Rational; N]> {
+    let mut rows: [[Rational; N]; N] = matrix.map(|row| row.map(Rational::from));
+    let mut values: [Rational; N] = rhs.map(Rational::from);
+
+    for col in 0..N {
+        let pivot = (col..N).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot);
+        values.swap(col, pivot);
+
+        for row in 0..N {
+            if row == col || rows[row][col].is_zero() {
+                continue;
+            }
+            let factor = rows[row][col] / rows[col][col];
+            for k in col..N {
+                rows[row][k] = rows[row][k] - factor * rows[col][k];
+            }
+            values[row] = values[row] - factor * values[col];
+        }
+    }
+
+    Some(std::array::from_fn(|i| values[i] / rows[i][i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        let (g, x, y) = extended_gcd(240, -46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x - 46 * y, 2);
+
+        assert_eq!(gcd(0, -7), 7);
+        assert_eq!(lcm(4, -6), 12);
+        assert_eq!(lcm_all([2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all([]), 1);
+
+        assert_eq!(mod_inverse(3, 101), Some(34));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // 101 and 103 as in the robot grid of day 14
+        let (x, m) = crt([(63, 101), (68, 103)]).unwrap();
+        assert_eq!((x % 101, x % 103, m), (63, 68, 10403));
+
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn rationals_stay_reduced() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half + Rational::new(3, 2), Rational::ONE);
+        assert_eq!((half * half).to_string(), "1/4");
+        assert_eq!(
+            (Rational::from(3) / Rational::new(3, 4)).to_integer(),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn solves_small_systems() {
+        let [a, b] = solve_linear([[94, 22], [34, 67]], [8400, 5400]).unwrap();
+        assert_eq!((a.to_integer(), b.to_integer()), (Some(80), Some(40)));
+
+        let [x, y, z] = solve_linear([[0, 1, 1], [2, 0, 1], [1, 1, 0]], [1, 1, 1]).unwrap();
+        assert_eq!(
+            [x, y, z],
+            [
+                Rational::new(1, 3),
+                Rational::new(2, 3),
+                Rational::new(1, 3)
+            ]
+        );
+
+        assert_eq!(solve_linear([[1, 2], [2, 4]], [3, 6]), None);
+    }
+}
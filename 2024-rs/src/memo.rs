@@ -0,0 +1,156 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+/// Cache for recursive solvers, the computation gets the memo back to recurse with.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// values currently cached
+    pub entries: usize,
+}
+
+impl MemoStats {
+    /// Share of lookups answered from the cache, 0 before the first lookup.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Drops every cached value, the statistics keep counting across clears.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_paths(row: u64, col: u64, memo: &mut Memo<(u64, u64), u64>) -> u64 {
+        if row == 0 || col == 0 {
+            return 1;
+        }
+        memo.get_or_insert_with((row, col), |memo| {
+            count_paths(row - 1, col, memo) + count_paths(row, col - 1, memo)
+        })
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        assert_eq!(count_paths(2, 2, &mut memo), 6);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 1,
+                misses: 4,
+                entries: 4
+            }
+        );
+        assert_eq!(memo.get(&(1, 1)), Some(&2));
+
+        assert_eq!(count_paths(2, 2, &mut memo), 6);
+        assert_eq!(memo.stats().hits, 2);
+        assert_eq!(
+            memo.stats().to_string(),
+            "2 hits, 4 misses (33.3% hit rate), 4 entries"
+        );
+    }
+
+    #[test]
+    fn clear_keeps_statistics() {
+        let mut memo = Memo::new();
+        count_paths(3, 3, &mut memo);
+        let misses = memo.stats().misses;
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().misses, misses);
+
+        count_paths(3, 3, &mut memo);
+        assert_eq!(memo.stats().misses, misses * 2);
+
+        memo.reset_stats();
+        assert_eq!(memo.stats().hit_rate(), 0.0);
+    }
+}
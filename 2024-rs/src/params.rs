@@ -0,0 +1,233 @@
+use std::{collections::HashMap, fmt::Display, fs::read_to_string, path::Path, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Preset {
+    /// values matching the examples from the puzzle text
+    Example,
+    /// values for the actual puzzle input
+    #[default]
+    Real,
+}
+
+impl FromStr for Preset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "example" => Ok(Preset::Example),
+            "real" => Ok(Preset::Real),
+            _ => Err(anyhow!(
+                "unknown preset '{}', available presets: example, real",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Preset::Example => write!(f, "example"),
+            Preset::Real => write!(f, "real"),
+        }
+    }
+}
+
+/// Tunable constants of a day, e.g. the grid size which differs between the example and the real input.
+pub trait Params: Sized {
+    fn preset(preset: Preset) -> Self;
+    /// Overrides a single value by its name.
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+}
+
+impl Params for () {
+    fn preset(_: Preset) -> Self {}
+
+    fn set(&mut self, key: &str, _: &str) -> Result<()> {
+        Err(anyhow!(
+            "unknown parameter '{}', this day has no parameters",
+            key
+        ))
+    }
+}
+
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| anyhow!("invalid value '{}' for parameter '{}'", value, key))
+}
+
+/// Like [`parse_value`], for values that have to be at least 1.
+pub fn parse_positive<T: FromStr + PartialOrd + From<u8>>(key: &str, value: &str) -> Result<T> {
+    let parsed: T = parse_value(key, value)?;
+    if parsed < T::from(1) {
+        return Err(anyhow!(
+            "parameter '{}' has to be positive, got '{}'",
+            key,
+            value
+        ));
+    }
+    Ok(parsed)
+}
+
+pub fn unknown_param(key: &str, available: &[&str]) -> anyhow::Error {
+    anyhow!(
+        "unknown parameter '{}', available parameters: {}",
+        key,
+        available.join(", ")
+    )
+}
+
+/// Preset choice plus `key=value` overrides, applied in order on top of the preset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParamSettings {
+    pub preset: Option<Preset>,
+    pub overrides: Vec<(String, String)>,
+}
+
+impl ParamSettings {
+    pub fn build<P: Params>(&self) -> Result<P> {
+        let mut params = P::preset(self.preset.unwrap_or_default());
+        self.overrides
+            .iter()
+            .try_for_each(|(key, value)| params.set(key, value))?;
+        Ok(params)
+    }
+
+    /// Layers `other` on top of these settings, its preset and overrides win.
+    pub fn merge(&self, other: &ParamSettings) -> ParamSettings {
+        ParamSettings {
+            preset: other.preset.or(self.preset),
+            overrides: self
+                .overrides
+                .iter()
+                .chain(other.overrides.iter())
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+pub fn parse_override(text: &str) -> Result<(String, String)> {
+    let (key, value) = text
+        .split_once('=')
+        .context(format!("expected key=value, got '{}'", text))?;
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// Parameter settings per day read from a TOML file such as
+///
+/// ```toml
+/// [day_14]
+/// preset = "example"
+/// width = 11
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParamConfig {
+    days: HashMap<u8, ParamSettings>,
+}
+
+impl ParamConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = read_to_string(path)
+            .context(format!("could not read config file '{}'", path.display()))?;
+        text.parse()
+            .context(format!("invalid config file '{}'", path.display()))
+    }
+
+    pub fn day(&self, day: u8) -> ParamSettings {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+}
+
+impl FromStr for ParamConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let table: toml::Table = s.parse()?;
+        let days = table
+            .into_iter()
+            .map(|(section, values)| {
+                let day = section
+                    .strip_prefix("day_")
+                    .and_then(|day| day.parse::<u8>().ok())
+                    .context(format!("expected a [day_XX] section, got '{}'", section))?;
+                let values = values
+                    .as_table()
+                    .context(format!("section '{}' is not a table", section))?;
+
+                let mut settings = ParamSettings::default();
+                for (key, value) in values {
+                    let value = match value {
+                        toml::Value::String(text) => text.clone(),
+                        value => value.to_string(),
+                    };
+                    if key == "preset" {
+                        settings.preset = Some(value.parse()?);
+                    } else {
+                        settings.overrides.push((key.clone(), value));
+                    }
+                }
+                Ok((day, settings))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(ParamConfig { days })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_sections_become_day_settings() {
+        let config: ParamConfig = "[day_14]\npreset = \"example\"\nwidth = 13\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            config.day(14),
+            ParamSettings {
+                preset: Some(Preset::Example),
+                overrides: vec![("width".to_string(), "13".to_string())],
+            }
+        );
+        assert_eq!(config.day(18), ParamSettings::default());
+    }
+
+    #[test]
+    fn config_rejects_unknown_sections() {
+        assert!("[day14]\nwidth = 13\n".parse::<ParamConfig>().is_err());
+        assert!("[day_14]\npreset = \"tiny\"\n"
+            .parse::<ParamConfig>()
+            .is_err());
+    }
+
+    #[test]
+    fn command_line_wins_over_config() {
+        let config = ParamSettings {
+            preset: Some(Preset::Example),
+            overrides: vec![("width".to_string(), "13".to_string())],
+        };
+        let cli = ParamSettings {
+            preset: None,
+            overrides: vec![("width".to_string(), "15".to_string())],
+        };
+        let merged = config.merge(&cli);
+        assert_eq!(merged.preset, Some(Preset::Example));
+        assert_eq!(merged.overrides.last().unwrap().1, "15");
+    }
+
+    #[test]
+    fn days_without_parameters_reject_overrides() {
+        let settings = ParamSettings {
+            preset: None,
+            overrides: vec![parse_override("width=1").unwrap()],
+        };
+        assert!(settings.build::<()>().is_err());
+        assert!(parse_override("width").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{cell::CharCell, grid::Grid};
+
+/// Parse failure pointing at a 1-based line and column of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A single input line together with its 1-based line number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, column: usize, message: impl Display) -> ParseError {
+        ParseError {
+            line: self.number,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    /// 1-based column where `part`, a slice of this line, starts.
+    pub fn column_of(&self, part: &str) -> usize {
+        let offset = (part.as_ptr() as usize).saturating_sub(self.text.as_ptr() as usize);
+        self.text
+            .get(..offset.min(self.text.len()))
+            .map_or(1, |before| before.chars().count() + 1)
+    }
+
+    /// Parses `part`, a slice of this line, reporting its column on failure.
+    pub fn parse<T: FromStr>(&self, part: &'a str) -> ParseResult<T> {
+        part.trim().parse().map_err(|_| {
+            self.error(
+                self.column_of(part.trim_start()),
+                format!("could not parse '{}'", part.trim()),
+            )
+        })
+    }
+
+    /// Every signed integer in the line, anything else is skipped, e.g. `p=0,-4` gives `0` and `-4`.
+    pub fn ints<T: FromStr>(&self) -> ParseResult<Vec<T>> {
+        let bytes = self.text.as_bytes();
+        let mut numbers = vec![];
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let signed =
+                matches!(bytes[i], b'-' | b'+') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+            if !signed && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            numbers.push(self.parse(&self.text[start..i])?);
+        }
+
+        Ok(numbers)
+    }
+
+    /// Exactly `N` signed integers.
+    pub fn ints_n<T: FromStr, const N: usize>(&self) -> ParseResult<[T; N]> {
+        let numbers = self.ints()?;
+        let found = numbers.len();
+        numbers
+            .try_into()
+            .map_err(|_| self.error(1, format!("expected {} numbers, found {}", N, found)))
+    }
+
+    /// Splits `key: value` around the first colon, both sides trimmed.
+    pub fn key_value(&self) -> ParseResult<(&'a str, &'a str)> {
+        self.text
+            .split_once(':')
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| self.error(1, format!("expected 'key: value', got '{}'", self.text)))
+    }
+}
+
+/// Numbered lines of the whole input.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    Section {
+        line: 1,
+        text: input,
+    }
+    .lines()
+}
+
+/// Block of lines separated from its neighbours by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// 1-based line number of the first line
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> {
+        let first = self.line;
+        self.text.lines().enumerate().map(move |(i, text)| Line {
+            number: first + i,
+            text,
+        })
+    }
+
+    /// Character grid, `cell` maps every character and its error is reported at that character.
+    pub fn grid<T>(&self, mut cell: impl FnMut(char) -> anyhow::Result<T>) -> ParseResult<Grid<T>> {
+        let mut width: Option<usize> = None;
+        let rows = self
+            .lines()
+            .map(|line| {
+                let row = line
+                    .text
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        cell(c).map_err(|error| line.error(i + 1, format!("{:#}", error)))
+                    })
+                    .collect::<ParseResult<Vec<T>>>()?;
+                match width {
+                    Some(width) if width != row.len() => Err(line.error(
+                        width.min(row.len()) + 1,
+                        format!("row has {} cells, expected {}", row.len(), width),
+                    )),
+                    _ => {
+                        width = Some(row.len());
+                        Ok(row)
+                    }
+                }
+            })
+            .collect::<ParseResult<Vec<Vec<T>>>>()?;
+
+        Ok(Grid::from_rows(rows).expect("rows have the same width"))
+    }
+
+    pub fn cells<T: CharCell>(&self) -> ParseResult<Grid<T>> {
+        self.grid(T::parse_char)
+    }
+}
+
+/// Splits on blank lines, line numbers keep counting across sections.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        match (start, is_blank) {
+            (None, false) => start = Some((i + 1, offset)),
+            (Some((line_number, from)), true) => {
+                sections.push(Section {
+                    line: line_number,
+                    text: input[from..offset].trim_end(),
+                });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((line_number, from)) = start {
+        sections.push(Section {
+            line: line_number,
+            text: input[from..].trim_end(),
+        });
+    }
+
+    sections
+}
+
+/// Exactly `N` sections, e.g. a grid followed by a list of moves.
+pub fn sections_n<const N: usize>(input: &str) -> ParseResult<[Section<'_>; N]> {
+    let sections = sections(input);
+    let found = sections.len();
+    let last_line = sections.last().map_or(1, |section| section.line);
+    sections.try_into().map_err(|_| ParseError {
+        line: last_line,
+        column: 1,
+        message: format!(
+            "expected {} sections separated by blank lines, found {}",
+            N, found
+        ),
+    })
+}
+
+/// Character grid of the whole input.
+pub fn grid<T>(input: &str, cell: impl FnMut(char) -> anyhow::Result<T>) -> ParseResult<Grid<T>> {
+    Section {
+        line: 1,
+        text: input,
+    }
+    .grid(cell)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn sections_keep_line_numbers() {
+        let input = "Register A: 729\nRegister B: 0\n\n\nProgram: 0,1,5\n";
+        let [registers, program] = sections_n(input).unwrap();
+        assert_eq!(registers.text, "Register A: 729\nRegister B: 0");
+        assert_eq!(program.line, 5);
+        assert_eq!(
+            program.lines().next().unwrap().key_value().unwrap(),
+            ("Program", "0,1,5")
+        );
+
+        let error = sections_n::<3>(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 1: expected 3 sections separated by blank lines, found 2"
+        );
+    }
+
+    #[test]
+    fn ints_skip_everything_else() {
+        let line = Line {
+            number: 1,
+            text: "p=0,-4 v=+3,-3 x-y",
+        };
+        assert_eq!(line.ints::<i64>().unwrap(), vec![0, -4, 3, -3]);
+        assert_eq!(
+            lines("3   4").next().unwrap().ints_n::<i32, 2>().unwrap(),
+            [3, 4]
+        );
+
+        let overflow = Line {
+            number: 7,
+            text: "X+1, Y+300",
+        };
+        assert_eq!(
+            overflow.ints::<u8>().unwrap_err().to_string(),
+            "line 7, column 7: could not parse '+300'"
+        );
+        assert!(overflow.ints_n::<i32, 3>().is_err());
+    }
+
+    #[test]
+    fn grid_errors_point_at_the_cell() {
+        let cell = |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(anyhow!("unknown cell '{}'", c)),
+        };
+        let [grid_section, _] = sections_n("#.\n.#\n\n<>").unwrap();
+        assert!(grid_section.grid(cell).unwrap()[(1, 1)]);
+
+        assert_eq!(
+            grid("#.\n.x", cell).unwrap_err().to_string(),
+            "line 2, column 2: unknown cell 'x'"
+        );
+        assert_eq!(
+            grid("#.\n.", cell).unwrap_err().to_string(),
+            "line 2, column 2: row has 1 cells, expected 2"
+        );
+        assert!(lines("no colon here").next().unwrap().key_value().is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+use crate::{direction::Dir8, grid::Pos};
+
+/// Point in the puzzles' `x,y` convention: `x` is the column, `y` is the row growing downwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// Converts a `(row, column)` grid position.
+    pub fn from_pos((row, col): Pos) -> Self {
+        Point::new(col as i64, row as i64)
+    }
+
+    /// The `(row, column)` grid position, `None` for negative coordinates.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    pub fn step(self, dir: impl Into<Dir8>) -> Point {
+        self + dir.into().delta()
+    }
+
+    /// Adds `delta`, `None` when the result leaves a `width` by `height` grid.
+    pub fn checked_add(self, delta: Point, width: usize, height: usize) -> Option<Point> {
+        let next = Point::new(self.x.checked_add(delta.x)?, self.y.checked_add(delta.y)?);
+        next.is_within(width, height).then_some(next)
+    }
+
+    pub fn is_within(self, width: usize, height: usize) -> bool {
+        (0..width as i64).contains(&self.x) && (0..height as i64).contains(&self.y)
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Wraps both coordinates into a `width` by `height` torus.
+    pub fn rem_euclid(self, width: i64, height: i64) -> Point {
+        Point::new(self.x.rem_euclid(width), self.y.rem_euclid(height))
+    }
+}
+
+impl From<Pos> for Point {
+    fn from(pos: Pos) -> Self {
+        Point::from_pos(pos)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Dir4;
+
+    #[test]
+    fn checked_add_stays_in_bounds() {
+        let corner = Point::new(0, 0);
+        assert_eq!(
+            corner.checked_add(Dir4::East.delta(), 3, 2),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(corner.checked_add(Dir4::North.delta(), 3, 2), None);
+        assert_eq!(Point::new(2, 1).checked_add(Point::new(1, 0), 3, 2), None);
+    }
+
+    #[test]
+    fn converts_to_grid_positions() {
+        let point = Point::new(4, 1);
+        assert_eq!(point.to_pos(), Some((1, 4)));
+        assert_eq!(Point::from_pos((1, 4)), point);
+        assert_eq!(Point::new(-1, 0).to_pos(), None);
+        assert_eq!(point.step(Dir4::South).to_string(), "4,2");
+        assert_eq!(point.manhattan(Point::new(1, 3)), 5);
+        assert_eq!(Point::new(-1, 7).rem_euclid(11, 7), Point::new(10, 0));
+    }
+}
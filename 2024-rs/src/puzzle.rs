@@ -0,0 +1,64 @@
+use std::fmt::{self, Display};
+
+use anyhow::Result;
+
+use crate::{params::Params, point::Point};
+
+/// Answer produced by a single part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    /// free-form text, e.g. day 17's comma-joined output
+    Text(String),
+    /// coordinate in the puzzle's `x,y` convention, e.g. day 18's blocking byte
+    Coordinate(i64, i64),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Coordinate(x, y) => write!(f, "{},{}", x, y),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_integer {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Answer {
+                fn from(value: $type) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_integer!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<Point> for Answer {
+    fn from(point: Point) -> Self {
+        Answer::Coordinate(point.x, point.y)
+    }
+}
+
+/// Common shape of every day: parse the raw input once, then solve each part.
+pub trait Solution {
+    type Input;
+    /// use `()` when the day has no tunable constants
+    type Params: Params;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input, params: &Self::Params) -> Result<Answer>;
+
+    fn part_two(input: &Self::Input, params: &Self::Params) -> Result<Answer>;
+}
@@ -0,0 +1,326 @@
+use std::collections::VecDeque;
+
+use crate::{
+    direction::{Dir4, Dir8},
+    grid::{Grid, Pos},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// cells touch through their edges only
+    Four,
+    /// diagonal neighbours touch as well
+    Eight,
+}
+
+impl Connectivity {
+    fn neighbours<T>(self, grid: &Grid<T>, pos: Pos) -> Vec<Pos> {
+        match self {
+            Connectivity::Four => grid.neighbours4(pos).collect(),
+            Connectivity::Eight => grid.neighbours8(pos).collect(),
+        }
+    }
+
+    /// Connectivity of the background, so that holes never leak through a diagonal gap.
+    fn dual(self) -> Connectivity {
+        match self {
+            Connectivity::Four => Connectivity::Eight,
+            Connectivity::Eight => Connectivity::Four,
+        }
+    }
+}
+
+/// Inclusive `(row, column)` corners of the smallest rectangle around a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    pub fn width(&self) -> usize {
+        self.max.1 - self.min.1 + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.0 - self.min.0 + 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub id: usize,
+    /// in the order they were reached, starting from the top left-most cell
+    pub cells: Vec<Pos>,
+    /// edges shared with cells outside of the region or with the border of the grid
+    pub perimeter: usize,
+    /// straight fence segments, equal to the number of corners
+    pub sides: usize,
+    pub bounds: Bounds,
+    /// enclosed areas of other cells, each counted once however large
+    pub holes: usize,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn has_holes(&self) -> bool {
+        self.holes > 0
+    }
+}
+
+/// Connected components of a grid together with a label for every cell.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    labels: Grid<Option<usize>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Neighbouring cells holding equal values form a region, every cell gets one.
+    pub fn of<T: PartialEq>(grid: &Grid<T>, connectivity: Connectivity) -> Self {
+        Regions::label(grid, connectivity, |_| true, |a, b| a == b)
+    }
+
+    /// Neighbouring cells accepted by `include` form a region, the rest is background.
+    pub fn matching<T>(
+        grid: &Grid<T>,
+        connectivity: Connectivity,
+        include: impl Fn(&T) -> bool,
+    ) -> Self {
+        Regions::label(grid, connectivity, include, |_, _| true)
+    }
+
+    fn label<T>(
+        grid: &Grid<T>,
+        connectivity: Connectivity,
+        include: impl Fn(&T) -> bool,
+        same: impl Fn(&T, &T) -> bool,
+    ) -> Self {
+        let mut labels: Grid<Option<usize>> = grid.map(|_| None);
+        let mut components: Vec<Vec<Pos>> = vec![];
+
+        for start in grid.positions() {
+            if labels[start].is_some() || !include(&grid[start]) {
+                continue;
+            }
+            let id = components.len();
+            let mut cells = vec![];
+            let mut queue: VecDeque<Pos> = VecDeque::from([start]);
+            labels[start] = Some(id);
+
+            while let Some(pos) = queue.pop_front() {
+                cells.push(pos);
+                for next in connectivity.neighbours(grid, pos) {
+                    if labels[next].is_none()
+                        && include(&grid[next])
+                        && same(&grid[pos], &grid[next])
+                    {
+                        labels[next] = Some(id);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            components.push(cells);
+        }
+
+        let regions = components
+            .into_iter()
+            .enumerate()
+            .map(|(id, cells)| measure(&labels, connectivity, id, cells))
+            .collect();
+
+        Regions { labels, regions }
+    }
+
+    pub fn label_at(&self, pos: Pos) -> Option<usize> {
+        self.labels.get(pos).copied().flatten()
+    }
+
+    pub fn region_at(&self, pos: Pos) -> Option<&Region> {
+        self.label_at(pos).map(|id| &self.regions[id])
+    }
+
+    pub fn labels(&self) -> &Grid<Option<usize>> {
+        &self.labels
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn largest(&self) -> Option<&Region> {
+        self.regions.iter().max_by_key(|region| region.area())
+    }
+}
+
+fn measure(
+    labels: &Grid<Option<usize>>,
+    connectivity: Connectivity,
+    id: usize,
+    cells: Vec<Pos>,
+) -> Region {
+    let inside = |pos: Pos, dir: Dir8| {
+        labels
+            .step(pos, dir)
+            .is_some_and(|next| labels[next] == Some(id))
+    };
+
+    let mut perimeter = 0;
+    let mut sides = 0;
+    for &pos in &cells {
+        for dir in Dir4::ALL {
+            let (side, turned) = (
+                inside(pos, dir.into()),
+                inside(pos, dir.turn_right().into()),
+            );
+            let diagonal = inside(pos, Dir8::from(dir).turn_right());
+
+            perimeter += usize::from(!side);
+            // an outer corner, or an inner one where the diagonal is missing
+            sides += usize::from((!side && !turned) || (side && turned && !diagonal));
+        }
+    }
+
+    let bounds = Bounds {
+        min: (
+            cells.iter().map(|pos| pos.0).min().unwrap_or_default(),
+            cells.iter().map(|pos| pos.1).min().unwrap_or_default(),
+        ),
+        max: (
+            cells.iter().map(|pos| pos.0).max().unwrap_or_default(),
+            cells.iter().map(|pos| pos.1).max().unwrap_or_default(),
+        ),
+    };
+
+    Region {
+        id,
+        holes: count_holes(labels, connectivity, id, bounds),
+        cells,
+        perimeter,
+        sides,
+        bounds,
+    }
+}
+
+/// Background components inside the bounding box that cannot reach its one cell wide margin.
+fn count_holes(
+    labels: &Grid<Option<usize>>,
+    connectivity: Connectivity,
+    id: usize,
+    bounds: Bounds,
+) -> usize {
+    // 0 = background, 1 = region, 2 = reached from the margin or an already counted hole
+    let mut area: Grid<u8> = Grid::new(bounds.width() + 2, bounds.height() + 2, 0);
+    for (row, col) in area.positions().collect::<Vec<_>>() {
+        let original = (
+            (row + bounds.min.0).checked_sub(1),
+            (col + bounds.min.1).checked_sub(1),
+        );
+        if let (Some(r), Some(c)) = original {
+            if labels.get((r, c)).copied().flatten() == Some(id) {
+                area[(row, col)] = 1;
+            }
+        }
+    }
+
+    let background = connectivity.dual();
+    let fill = |area: &mut Grid<u8>, start: Pos| {
+        let mut queue: VecDeque<Pos> = VecDeque::from([start]);
+        area[start] = 2;
+        while let Some(pos) = queue.pop_front() {
+            for next in background.neighbours(area, pos) {
+                if area[next] == 0 {
+                    area[next] = 2;
+                    queue.push_back(next);
+                }
+            }
+        }
+    };
+
+    fill(&mut area, (0, 0));
+
+    let mut holes = 0;
+    for pos in area.positions().collect::<Vec<_>>() {
+        if area[pos] == 0 {
+            holes += 1;
+            fill(&mut area, pos);
+        }
+    }
+    holes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_every_region() {
+        let garden: Grid<char> = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC", Ok).unwrap();
+        let regions = Regions::of(&garden, Connectivity::Four);
+
+        let metrics: Vec<(char, usize, usize, usize)> = regions
+            .iter()
+            .map(|region| {
+                (
+                    garden[region.cells[0]],
+                    region.area(),
+                    region.perimeter,
+                    region.sides,
+                )
+            })
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+        assert_eq!(regions.region_at((2, 3)).unwrap().id, 2);
+        assert_eq!(
+            regions.region_at((1, 2)).unwrap().bounds,
+            Bounds {
+                min: (1, 2),
+                max: (3, 3)
+            }
+        );
+    }
+
+    #[test]
+    fn finds_holes() {
+        let garden: Grid<char> = Grid::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO", Ok).unwrap();
+        let regions = Regions::of(&garden, Connectivity::Four);
+        let outer = regions.region_at((0, 0)).unwrap();
+        assert_eq!(outer.holes, 4);
+        assert_eq!(outer.sides, 20);
+        assert!(!regions.region_at((1, 1)).unwrap().has_holes());
+    }
+
+    #[test]
+    fn diagonal_gaps_depend_on_connectivity() {
+        let ring: Grid<bool> = Grid::parse(".#.\n#.#\n.#.", |c| Ok(c == '#')).unwrap();
+
+        let four = Regions::matching(&ring, Connectivity::Four, |&cell| cell);
+        assert_eq!(four.len(), 4);
+        assert!(four.iter().all(|region| !region.has_holes()));
+
+        let eight = Regions::matching(&ring, Connectivity::Eight, |&cell| cell);
+        assert_eq!(eight.len(), 1);
+        assert_eq!(eight.largest().unwrap().holes, 1);
+        assert_eq!(eight.label_at((1, 1)), None);
+    }
+}
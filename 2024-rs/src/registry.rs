@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+
+use crate::{
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09, day_10, day_11, day_12,
+    day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22,
+    params::ParamSettings,
+    puzzle::{Answer, Solution},
+};
+
+/// Parses the input once and solves the given parts, timing every stage.
+pub type Runner = fn(&str, &ParamSettings, &[u8]) -> Result<TimedRun>;
+
+#[derive(Debug)]
+pub struct TimedPart {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct TimedRun {
+    pub parse: Duration,
+    pub parts: Vec<TimedPart>,
+}
+
+fn run_timed<S: Solution>(input: &str, settings: &ParamSettings, parts: &[u8]) -> Result<TimedRun> {
+    let params = settings.build::<S::Params>()?;
+
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part_one(&parsed, &params),
+                _ => S::part_two(&parsed, &params),
+            };
+            TimedPart {
+                part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(TimedRun { parse, parts })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    runner: Runner,
+}
+
+pub const DAYS: [Day; 22] = [
+    Day::of::<day_01::day_01_solution::solution::Day01>(1),
+    Day::of::<day_02::day_02_solution::solution::Day02>(2),
+    Day::of::<day_03::day_03_solution::solution::Day03>(3),
+    Day::of::<day_04::solution::Day04>(4),
+    Day::of::<day_05::solution::Day05>(5),
+    Day::of::<day_06::solution::Day06>(6),
+    Day::of::<day_07::solution::Day07>(7),
+    Day::of::<day_08::solution::Day08>(8),
+    Day::of::<day_09::solution::Day09>(9),
+    Day::of::<day_10::solution::Day10>(10),
+    Day::of::<day_11::solution::Day11>(11),
+    Day::of::<day_12::solution::Day12>(12),
+    Day::of::<day_13::solution::Day13>(13),
+    Day::of::<day_14::solution::Day14>(14),
+    Day::of::<day_15::solution::Day15>(15),
+    Day::of::<day_16::solution::Day16>(16),
+    Day::of::<day_17::solution::Day17>(17),
+    Day::of::<day_18::solution::Day18>(18),
+    Day::of::<day_19::solution::Day19>(19),
+    Day::of::<day_20::solution::Day20>(20),
+    Day::of::<day_21::solution::Day21>(21),
+    Day::of::<day_22::solution::Day22>(22),
+];
+
+pub fn find_day(number: u8) -> Result<&'static Day> {
+    DAYS.iter().find(|day| day.number == number).ok_or_else(|| {
+        anyhow!(
+            "day {} is not available, available days: {}",
+            number,
+            DAYS.iter().map(|day| day.number).join(", ")
+        )
+    })
+}
+
+impl Day {
+    pub const PARTS: [u8; 2] = [1, 2];
+
+    pub(crate) const fn of<S: Solution>(number: u8) -> Self {
+        Day {
+            number,
+            runner: run_timed::<S>,
+        }
+    }
+
+    pub fn check_part(&self, part: u8) -> Result<()> {
+        match Day::PARTS.contains(&part) {
+            true => Ok(()),
+            false => Err(anyhow!(
+                "part {} is not available for day {}, available parts: 1, 2",
+                part,
+                self.number
+            )),
+        }
+    }
+
+    pub fn run(&self, input: &str, settings: &ParamSettings, parts: &[u8]) -> Result<TimedRun> {
+        parts.iter().try_for_each(|&part| self.check_part(part))?;
+        (self.runner)(input, settings, parts)
+    }
+}
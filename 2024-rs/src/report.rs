@@ -0,0 +1,140 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::runner::PartRecord;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(anyhow!(
+                "unknown format '{}', available formats: text, json, csv",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// Flat shape of a record shared by the JSON and CSV output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Row {
+    pub day: u8,
+    pub part: u8,
+    /// always a string, integer answers may not fit into a JSON number
+    pub answer: Option<String>,
+    pub time_ns: Option<u64>,
+    pub status: &'static str,
+    pub error: Option<String>,
+}
+
+impl From<&PartRecord> for Row {
+    fn from(record: &PartRecord) -> Self {
+        Row {
+            day: record.day,
+            part: record.part,
+            answer: record.answer().map(|answer| answer.to_string()),
+            time_ns: record.elapsed.map(|elapsed| elapsed.as_nanos() as u64),
+            status: record.status(),
+            error: record.error().map(str::to_string),
+        }
+    }
+}
+
+pub fn to_json(records: &[PartRecord]) -> Result<String> {
+    let rows: Vec<Row> = records.iter().map(Row::from).collect();
+    Ok(serde_json::to_string_pretty(&rows)?)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_csv(records: &[PartRecord]) -> String {
+    let mut csv = String::from("day,part,answer,time_ns,status,error\n");
+    records.iter().map(Row::from).for_each(|row| {
+        let fields = [
+            row.day.to_string(),
+            row.part.to_string(),
+            row.answer.unwrap_or_default(),
+            row.time_ns.map(|time| time.to_string()).unwrap_or_default(),
+            row.status.to_string(),
+            row.error.unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    });
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{puzzle::Answer, runner::Outcome};
+
+    fn records() -> Vec<PartRecord> {
+        vec![
+            PartRecord {
+                day: 17,
+                part: 1,
+                outcome: Outcome::Solved(Answer::Text("4,6,3".to_string())),
+                elapsed: Some(Duration::from_nanos(1500)),
+            },
+            PartRecord {
+                day: 17,
+                part: 2,
+                outcome: Outcome::Panicked("unexpected operand '7'".to_string()),
+                elapsed: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_commas() {
+        assert_eq!(
+            to_csv(&records()),
+            "day,part,answer,time_ns,status,error\n\
+             17,1,\"4,6,3\",1500,ok,\n\
+             17,2,,,panic,unexpected operand '7'\n"
+        );
+    }
+
+    #[test]
+    fn json_has_one_record_per_part() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&records()).unwrap()).unwrap();
+        assert_eq!(json[0]["answer"], "4,6,3");
+        assert_eq!(json[0]["time_ns"], 1500);
+        assert_eq!(json[1]["status"], "panic");
+        assert_eq!(json[1]["answer"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    any::Any,
+    fmt::{self, Display},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread::{available_parallelism, scope},
+    time::Duration,
+};
+
+use anyhow::Result;
+
+use crate::{
+    params::ParamSettings,
+    puzzle::Answer,
+    registry::{Day, TimedPart},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    Failed(String),
+    /// the solver panicked, with the panic message
+    Panicked(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRecord {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+    /// time spent in the part itself, missing when it did not finish
+    pub elapsed: Option<Duration>,
+}
+
+impl PartRecord {
+    pub fn from_timed(day: u8, timed: TimedPart) -> Self {
+        let (outcome, elapsed) = match timed.answer {
+            Ok(answer) => (Outcome::Solved(answer), Some(timed.elapsed)),
+            Err(error) => (Outcome::Failed(format!("{:#}", error)), None),
+        };
+        PartRecord {
+            day,
+            part: timed.part,
+            outcome,
+            elapsed,
+        }
+    }
+
+    /// Record of a part that could not run, e.g. because its input failed to load or parse.
+    pub fn failed(day: u8, part: u8, error: &anyhow::Error) -> Self {
+        PartRecord {
+            day,
+            part,
+            outcome: Outcome::Failed(format!("{:#}", error)),
+            elapsed: None,
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self.outcome {
+            Outcome::Solved(_) => "ok",
+            Outcome::Failed(_) => "error",
+            Outcome::Panicked(_) => "panic",
+        }
+    }
+
+    pub fn answer(&self) -> Option<&Answer> {
+        match &self.outcome {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Solved(_) => None,
+            Outcome::Failed(message) | Outcome::Panicked(message) => Some(message),
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Solves a single part, turning errors and panics into the record's outcome.
+pub fn run_part(day: &Day, input: &str, settings: &ParamSettings, part: u8) -> PartRecord {
+    match catch_unwind(AssertUnwindSafe(|| day.run(input, settings, &[part]))) {
+        Ok(Ok(mut run)) => PartRecord::from_timed(day.number, run.parts.remove(0)),
+        Ok(Err(error)) => PartRecord::failed(day.number, part, &error),
+        Err(payload) => PartRecord {
+            day: day.number,
+            part,
+            outcome: Outcome::Panicked(panic_message(payload)),
+            elapsed: None,
+        },
+    }
+}
+
+/// Solves both parts of a day from a single parse, like a single-day run, so the timings
+/// leave out parsing. When the day panics the parts are rerun one by one, so a panic in one
+/// part does not cost the other its answer.
+pub fn run_day(day: &Day, input: &str, settings: &ParamSettings) -> Vec<PartRecord> {
+    match catch_unwind(AssertUnwindSafe(|| day.run(input, settings, &Day::PARTS))) {
+        Ok(Ok(run)) => run
+            .parts
+            .into_iter()
+            .map(|part| PartRecord::from_timed(day.number, part))
+            .collect(),
+        Ok(Err(error)) => Day::PARTS
+            .iter()
+            .map(|&part| PartRecord::failed(day.number, part, &error))
+            .collect(),
+        Err(_) => Day::PARTS
+            .iter()
+            .map(|&part| run_part(day, input, settings, part))
+            .collect(),
+    }
+}
+
+/// A day together with its loaded input and parameters.
+pub struct Job<'a> {
+    pub day: &'a Day,
+    pub input: Result<String>,
+    pub settings: ParamSettings,
+}
+
+/// Runs every job on up to `workers` threads, one day per task, records come back ordered by
+/// day and part.
+pub fn run_parallel(jobs: &[Job], workers: Option<usize>) -> Vec<PartRecord> {
+    let workers = workers
+        .or_else(|| available_parallelism().ok().map(|count| count.get()))
+        .unwrap_or(1)
+        .clamp(1, jobs.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let records = Mutex::new(Vec::with_capacity(jobs.len() * Day::PARTS.len()));
+
+    scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(job) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let day_records = match &job.input {
+                        Ok(input) => run_day(job.day, input, &job.settings),
+                        Err(error) => Day::PARTS
+                            .iter()
+                            .map(|&part| PartRecord::failed(job.day.number, part, error))
+                            .collect(),
+                    };
+                    records
+                        .lock()
+                        .expect("no worker panics while holding the lock")
+                        .extend(day_records);
+                }
+            });
+        }
+    });
+
+    let mut records = records.into_inner().expect("workers are done");
+    records.sort_by_key(|record| (record.day, record.part));
+    records
+}
+
+pub struct SummaryTable<'a>(pub &'a [PartRecord]);
+
+impl Display for SummaryTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:>4}  {:<20}  {:>10}  status",
+            "day", "part", "answer", "time"
+        )?;
+        for record in self.0 {
+            let status = match record.error() {
+                Some(error) => format!("{}: {}", record.status(), error),
+                None => record.status().to_string(),
+            };
+            writeln!(
+                f,
+                "{:>3}  {:>4}  {:<20}  {:>10}  {}",
+                record.day,
+                record.part,
+                record.answer().map_or("-".to_string(), Answer::to_string),
+                record
+                    .elapsed
+                    .map_or("-".to_string(), |elapsed| format!("{:.2?}", elapsed)),
+                status
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+    use crate::{puzzle::Solution, registry::find_day};
+
+    /// Panics in part one and solves part two.
+    struct Panicking;
+
+    impl Solution for Panicking {
+        type Input = ();
+        type Params = ();
+
+        fn parse(_: &str) -> Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_one(_: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            panic!("part one always panics")
+        }
+
+        fn part_two(_: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            Ok(Answer::from(2))
+        }
+    }
+
+    const PANICKING: Day = Day::of::<Panicking>(99);
+
+    static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+    /// Counts how often its input is parsed.
+    struct Counting;
+
+    impl Solution for Counting {
+        type Input = ();
+        type Params = ();
+
+        fn parse(_: &str) -> Result<Self::Input> {
+            PARSES.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        fn part_one(_: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            Ok(Answer::from(1))
+        }
+
+        fn part_two(_: &Self::Input, _: &Self::Params) -> Result<Answer> {
+            Ok(Answer::from(2))
+        }
+    }
+
+    const COUNTING: Day = Day::of::<Counting>(98);
+
+    #[test]
+    fn panics_and_errors_do_not_stop_other_days() {
+        let settings = ParamSettings::default();
+        let jobs = vec![
+            Job {
+                day: &PANICKING,
+                input: Ok(String::new()),
+                settings: settings.clone(),
+            },
+            Job {
+                day: find_day(9).unwrap(),
+                input: Ok("2333133121414131402".to_string()),
+                settings: settings.clone(),
+            },
+            Job {
+                day: find_day(3).unwrap(),
+                input: Err(anyhow!("no input")),
+                settings,
+            },
+        ];
+
+        let records = run_parallel(&jobs, Some(2));
+        let statuses: Vec<(u8, u8, &str)> = records
+            .iter()
+            .map(|record| (record.day, record.part, record.status()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (3, 1, "error"),
+                (3, 2, "error"),
+                (9, 1, "ok"),
+                (9, 2, "ok"),
+                (99, 1, "panic"),
+                (99, 2, "ok"),
+            ]
+        );
+        assert_eq!(records[2].answer(), Some(&Answer::from(1928)));
+        assert_eq!(records[4].error(), Some("part one always panics"));
+        assert_eq!(records[5].answer(), Some(&Answer::from(2)));
+    }
+
+    #[test]
+    fn both_parts_share_one_parse() {
+        let jobs = vec![Job {
+            day: &COUNTING,
+            input: Ok(String::new()),
+            settings: ParamSettings::default(),
+        }];
+
+        let records = run_parallel(&jobs, Some(2));
+        assert_eq!(PARSES.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.part, record.answer().cloned()))
+                .collect::<Vec<_>>(),
+            vec![(1, Some(Answer::from(1))), (2, Some(Answer::from(2)))]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Everything learned by a search: the cost of every settled state and, for each of them,
+/// all predecessors lying on a cheapest way to it.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    start: S,
+    dist: HashMap<S, u64>,
+    predecessors: HashMap<S, Vec<S>>,
+    /// goal states reached at the lowest cost
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        Search {
+            dist: HashMap::from([(start.clone(), 0)]),
+            predecessors: HashMap::new(),
+            goals: vec![],
+            start,
+        }
+    }
+
+    /// Records reaching `next` from `state` at `cost`, true when that is a new best.
+    fn relax(&mut self, state: &S, next: S, cost: u64) -> bool {
+        // nothing beats the start, and a zero-cost way back to it would make paths loop
+        if next == self.start {
+            return false;
+        }
+        match self.dist.get(&next) {
+            Some(&known) if cost > known => false,
+            Some(&known) if cost == known => {
+                self.predecessors
+                    .entry(next)
+                    .or_default()
+                    .push(state.clone());
+                false
+            }
+            _ => {
+                self.dist.insert(next.clone(), cost);
+                self.predecessors.insert(next, vec![state.clone()]);
+                true
+            }
+        }
+    }
+
+    pub fn start(&self) -> &S {
+        &self.start
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.dist.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, u64> {
+        &self.dist
+    }
+
+    pub fn into_distances(self) -> HashMap<S, u64> {
+        self.dist
+    }
+
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    /// Lowest cost of reaching a goal.
+    pub fn cost(&self) -> Option<u64> {
+        self.goals.first().and_then(|goal| self.distance(goal))
+    }
+
+    /// Predecessors of `state` on its cheapest paths, empty for the start.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One cheapest path from the start to `target`, both included.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        self.dist.get(target)?;
+
+        let mut path = vec![target.clone()];
+        while let Some(previous) = self.predecessors(path.last()?).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// One cheapest path to the first goal.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+
+    /// Every state lying on at least one cheapest path to any of `targets`.
+    pub fn on_best_paths<'a>(&'a self, targets: impl IntoIterator<Item = &'a S>) -> HashSet<S> {
+        let mut stack: Vec<&S> = targets
+            .into_iter()
+            .filter(|target| self.dist.contains_key(target))
+            .collect();
+        let mut seen: HashSet<S> = stack.iter().map(|&state| state.clone()).collect();
+
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors(state) {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous);
+                }
+            }
+        }
+
+        seen
+    }
+}
+
+/// Breadth-first search over unit-cost edges. Stops once every goal at the lowest
+/// distance is found, pass `|_| false` to explore everything reachable.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue: VecDeque<(S, u64)> = VecDeque::from([(start, 0)]);
+
+    while let Some((state, cost)) = queue.pop_front() {
+        if search.cost().is_some_and(|best| cost > best) {
+            break;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+        for next in successors(&state) {
+            if search.relax(&state, next.clone(), cost + 1) {
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm over `(state, cost)` successors.
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+/// A* search, `heuristic` has to be consistent and never overestimate the remaining cost.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut search = Search::new(start.clone());
+    let mut heap: BinaryHeap<Entry<S>> = BinaryHeap::new();
+    heap.push(Entry {
+        priority: heuristic(&start),
+        cost: 0,
+        state: start,
+    });
+
+    while let Some(Entry {
+        priority,
+        cost,
+        state,
+    }) = heap.pop()
+    {
+        if search.cost().is_some_and(|best| priority > best) {
+            break;
+        }
+        if search.distance(&state).is_some_and(|known| cost > known) {
+            continue;
+        }
+        if is_goal(&state) {
+            search.goals.push(state);
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if search.relax(&state, next.clone(), next_cost) {
+                heap.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    search
+}
+
+/// Heap entry ordered so that the lowest priority is popped first.
+struct Entry<S> {
+    priority: u64,
+    cost: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.priority, self.cost) == (other.priority, other.cost)
+    }
+}
+
+impl<S> Eq for Entry<S> {}
+
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.priority, other.cost).cmp(&(self.priority, self.cost))
+    }
+}
+
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    /// a -> b -> d and a -> c -> d tie, a -> d directly is more expensive
+    fn diamond(state: &char) -> Vec<(char, u64)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 2), ('d', 5)],
+            'b' => vec![('d', 2)],
+            'c' => vec![('d', 1)],
+            'd' => vec![('e', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn dijkstra_keeps_every_best_predecessor() {
+        let search = dijkstra('a', diamond, |&state| state == 'd');
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.goals(), &['d']);
+        assert_eq!(search.predecessors(&'d'), &['b', 'c']);
+        assert_eq!(search.path(), Some(vec!['a', 'b', 'd']));
+        assert_eq!(
+            search.on_best_paths(search.goals()),
+            HashSet::from(['a', 'b', 'c', 'd'])
+        );
+        assert_eq!(search.distance(&'e'), None);
+    }
+
+    #[test]
+    fn bfs_and_astar_agree_on_a_maze() {
+        let maze = Grid::parse("..#\n#..\n...", |c| Ok(c == '#')).unwrap();
+        let end: Pos = (2, 0);
+        let open =
+            |&pos: &Pos| -> Vec<Pos> { maze.neighbours4(pos).filter(|&n| !maze[n]).collect() };
+
+        let by_bfs = bfs((0, 0), open, |&pos| pos == end);
+        let by_astar = astar(
+            (0, 0),
+            |pos| open(pos).into_iter().map(|next| (next, 1)),
+            |&(row, col)| (row.abs_diff(end.0) + col.abs_diff(end.1)) as u64,
+            |&pos| pos == end,
+        );
+
+        assert_eq!(by_bfs.cost(), Some(4));
+        assert_eq!(by_astar.cost(), Some(4));
+        assert_eq!(by_bfs.path().map(|path| path.len()), Some(5));
+        assert_eq!(by_bfs.path(), by_astar.path());
+    }
+
+    #[test]
+    fn zero_cost_edges_back_to_the_start_are_ignored() {
+        let search = dijkstra(
+            'a',
+            |state| match state {
+                'a' => vec![('b', 0)],
+                'b' => vec![('a', 0), ('c', 1)],
+                _ => vec![],
+            },
+            |&state| state == 'c',
+        );
+        assert_eq!(search.predecessors(&'a'), &[] as &[char]);
+        assert_eq!(search.path(), Some(vec!['a', 'b', 'c']));
+        assert_eq!(search.path_to(&'a'), Some(vec!['a']));
+    }
+}